@@ -22,6 +22,8 @@ pub struct Config {
 pub enum DatabaseConfig {
     Sqlite(SqliteConfig),
     LibSql(LibSqlConfig),
+    Postgres(PostgresConfig),
+    Mysql(MysqlConfig),
 }
 
 #[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
@@ -50,25 +52,125 @@ impl From<SqliteConfig> for crate::AnyOptions {
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum LibSqlConfig {
     Memory,
-    Path(PathBuf),
+    Path {
+        path: PathBuf,
+    },
+    /// An embedded replica at `path`, kept in sync with the primary at `url`
+    Replica {
+        path: PathBuf,
+        /// A libSQL server, or the path of a local database file
+        url: String,
+        #[serde(default)]
+        auth_token: String,
+        /// Seconds between syncs
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sync_interval: Option<u64>,
+    },
 }
 
 #[cfg(feature = "libsql")]
-impl From<LibSqlConfig> for AnyOptions {
-    fn from(value: LibSqlConfig) -> Self {
+impl TryFrom<LibSqlConfig> for AnyOptions {
+    type Error = AnyError;
+
+    fn try_from(value: LibSqlConfig) -> Result<Self, Self::Error> {
         use usql_libsql::Options;
         let opts = match value {
-            LibSqlConfig::Memory => Options {
-                path: None,
-                flags: Default::default(),
-            },
-            LibSqlConfig::Path(path) => Options {
-                path: Some(path),
-                flags: Default::default(),
-            },
+            LibSqlConfig::Memory => Options::default(),
+            LibSqlConfig::Path { path } => Options::default().path(path),
+            #[cfg(feature = "libsql-replication")]
+            LibSqlConfig::Replica {
+                path,
+                url,
+                auth_token,
+                sync_interval,
+            } => {
+                let mut replica = usql_libsql::ReplicaOptions::new(url, auth_token);
+                if let Some(secs) = sync_interval {
+                    replica = replica.sync_interval(std::time::Duration::from_secs(secs));
+                }
+                Options::default().replica(path, replica)
+            }
+            #[cfg(not(feature = "libsql-replication"))]
+            LibSqlConfig::Replica { .. } => {
+                return Err(AnyError::Message("Libsql replication feature not enabled"));
+            }
         };
 
-        opts.into()
+        Ok(opts.into())
+    }
+}
+
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PostgresConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub db: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_size: Option<usize>,
+}
+
+#[cfg(feature = "postgres")]
+impl From<PostgresConfig> for AnyOptions {
+    fn from(value: PostgresConfig) -> Self {
+        use usql_postgres::{Config, PoolConfig, PostgresOptions};
+        let config = Config {
+            host: value.host,
+            port: value.port,
+            user: value.user,
+            password: value.password,
+            dbname: value.db,
+            pool: value.pool_size.map(PoolConfig::new),
+            ..Default::default()
+        };
+
+        PostgresOptions::new(config).into()
+    }
+}
+
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MysqlConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub db: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_size: Option<usize>,
+}
+
+#[cfg(feature = "mysql")]
+impl From<MysqlConfig> for AnyOptions {
+    fn from(value: MysqlConfig) -> Self {
+        use usql_mysql::{MysqlOptions, OptsBuilder, PoolConstraints, PoolOpts};
+        let mut builder = OptsBuilder::default()
+            .ip_or_hostname(value.host.unwrap_or_else(|| "localhost".into()))
+            .tcp_port(value.port.unwrap_or(3306))
+            .user(value.user)
+            .pass(value.password)
+            .db_name(value.db);
+
+        if let Some(constraints) = value
+            .pool_size
+            .and_then(|size| PoolConstraints::new(0, size))
+        {
+            builder = builder.pool_opts(PoolOpts::default().with_constraints(constraints));
+        }
+
+        MysqlOptions::from(builder).into()
     }
 }
 
@@ -88,11 +190,28 @@ impl Config {
             }
             DatabaseConfig::LibSql(lib_sql_config) => {
                 #[cfg(feature = "libsql")]
-                let pool = AnyConnector::create_pool(lib_sql_config.into()).await;
+                let pool = match lib_sql_config.try_into() {
+                    Ok(options) => AnyConnector::create_pool(options).await,
+                    Err(err) => Err(err),
+                };
                 #[cfg(not(feature = "libsql"))]
                 let pool = Err(AnyError::Message("Libsql feature not enabled"));
                 pool
             }
+            DatabaseConfig::Postgres(postgres_config) => {
+                #[cfg(feature = "postgres")]
+                let pool = AnyConnector::create_pool(postgres_config.into()).await;
+                #[cfg(not(feature = "postgres"))]
+                let pool = Err(AnyError::Message("Postgres feature not enabled"));
+                pool
+            }
+            DatabaseConfig::Mysql(mysql_config) => {
+                #[cfg(feature = "mysql")]
+                let pool = AnyConnector::create_pool(mysql_config.into()).await;
+                #[cfg(not(feature = "mysql"))]
+                let pool = Err(AnyError::Message("Mysql feature not enabled"));
+                pool
+            }
         }
     }
 }
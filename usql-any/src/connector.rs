@@ -3,15 +3,25 @@ use core::task::{Poll, ready};
 use futures_core::{Stream, stream::BoxStream};
 use pin_project_lite::pin_project;
 
-#[cfg(feature = "postgres")]
-use super::postgres_impl::*;
-use usql_core::{ColumnIndex, Connector, Executor, QueryStream, Transaction};
-use usql_core::{Connection, DatabaseInfo, Pool, Row, Statement};
+use usql_core::{ColumnIndex, Connector, Executor, QueryResult, QueryStream, Transaction};
+use usql_core::{
+    Connection, DatabaseError, DatabaseInfo, ErrorKind, Pool, Row, Statement, TransactionOptions,
+};
 #[cfg(feature = "libsql")]
 use usql_libsql::{
     Conn as LibSqlConn, Error as LibSqlError, LibSql, LibSqlInfo, Options as LibSqlOptions,
     Pool as LibSqlPool, Row as LibSqlRow, Stmt as LibSqlStatement, Trans as LibSqlTransaction,
 };
+#[cfg(feature = "mysql")]
+use usql_mysql::{
+    Mysql, MysqlConn, MysqlDatabaseInfo, MysqlError, MysqlOptions, MysqlPool, MysqlRow,
+    MysqlStatement, MysqlTransaction,
+};
+#[cfg(feature = "postgres")]
+use usql_postgres::{
+    Postgres, PostgresConn, PostgresDatabaseInfo, PostgresError, PostgresOptions, PostgresPool,
+    PostgresRow, PostgresStatement, PostgresTransaction,
+};
 #[cfg(feature = "sqlite")]
 use usql_sqlite::{
     Sqlite, SqliteConn, SqliteDatabaseInfo, SqliteError, SqliteOptions, SqlitePool, SqliteRow,
@@ -21,7 +31,7 @@ use usql_value::{Type, ValueCow};
 
 macro_rules! missing_db {
     () => {
-        panic!("Enable at least one of the following features: sqlite, libsql")
+        panic!("Enable at least one of the following features: sqlite, libsql, postgres, mysql")
     };
 }
 
@@ -58,6 +68,16 @@ impl Connector for AnyConnector {
                     .await
                     .map(AnyPool::Libsql)
                     .map_err(AnyError::LibSql),
+                #[cfg(feature = "postgres")]
+                AnyOptions::Postgres(options) => Postgres::create_pool(options)
+                    .await
+                    .map(AnyPool::Postgres)
+                    .map_err(AnyError::Postgres),
+                #[cfg(feature = "mysql")]
+                AnyOptions::Mysql(options) => Mysql::create_pool(options)
+                    .await
+                    .map(AnyPool::Mysql)
+                    .map_err(AnyError::Mysql),
                 _ => missing_db!(),
             }
         }
@@ -71,6 +91,10 @@ pub enum AnyOptions {
     Sqlite(SqliteOptions),
     #[cfg(feature = "libsql")]
     Libsql(LibSqlOptions),
+    #[cfg(feature = "postgres")]
+    Postgres(PostgresOptions),
+    #[cfg(feature = "mysql")]
+    Mysql(MysqlOptions),
 }
 
 #[cfg(feature = "sqlite")]
@@ -87,6 +111,20 @@ impl From<LibSqlOptions> for AnyOptions {
     }
 }
 
+#[cfg(feature = "postgres")]
+impl From<PostgresOptions> for AnyOptions {
+    fn from(value: PostgresOptions) -> Self {
+        Self::Postgres(value)
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl From<MysqlOptions> for AnyOptions {
+    fn from(value: MysqlOptions) -> Self {
+        Self::Mysql(value)
+    }
+}
+
 #[derive(Clone)]
 #[non_exhaustive]
 pub enum AnyPool {
@@ -94,6 +132,10 @@ pub enum AnyPool {
     Sqlite(SqlitePool),
     #[cfg(feature = "libsql")]
     Libsql(LibSqlPool),
+    #[cfg(feature = "postgres")]
+    Postgres(PostgresPool),
+    #[cfg(feature = "mysql")]
+    Mysql(MysqlPool),
 }
 
 impl Pool for AnyPool {
@@ -122,6 +164,18 @@ impl Pool for AnyPool {
                     .await
                     .map(AnyConn::Libsql)
                     .map_err(AnyError::LibSql),
+                #[cfg(feature = "postgres")]
+                AnyPool::Postgres(pool) => pool
+                    .get()
+                    .await
+                    .map(AnyConn::Postgres)
+                    .map_err(AnyError::Postgres),
+                #[cfg(feature = "mysql")]
+                AnyPool::Mysql(pool) => pool
+                    .get()
+                    .await
+                    .map(AnyConn::Mysql)
+                    .map_err(AnyError::Mysql),
                 _ => missing_db!(),
             }
         }
@@ -134,6 +188,10 @@ pub enum AnyInfo {
     Sqlite(SqliteDatabaseInfo),
     #[cfg(feature = "libsql")]
     Libsql(LibSqlInfo),
+    #[cfg(feature = "postgres")]
+    Postgres(PostgresDatabaseInfo),
+    #[cfg(feature = "mysql")]
+    Mysql(MysqlDatabaseInfo),
 }
 
 impl DatabaseInfo for AnyInfo {
@@ -144,6 +202,10 @@ impl DatabaseInfo for AnyInfo {
             AnyInfo::Sqlite(_) => usql_core::System::Sqlite,
             #[cfg(feature = "libsql")]
             AnyInfo::Libsql(_) => usql_core::System::LibSql,
+            #[cfg(feature = "postgres")]
+            AnyInfo::Postgres(_) => usql_core::System::Postgres,
+            #[cfg(feature = "mysql")]
+            AnyInfo::Mysql(_) => usql_core::System::Mysql,
             _ => missing_db!(),
         }
     }
@@ -155,13 +217,19 @@ pub enum AnyConn {
     Sqlite(SqliteConn),
     #[cfg(feature = "libsql")]
     Libsql(LibSqlConn),
+    #[cfg(feature = "postgres")]
+    Postgres(PostgresConn),
+    #[cfg(feature = "mysql")]
+    Mysql(MysqlConn),
 }
 
 impl Connection for AnyConn {
     type Transaction<'conn> = AnyTransaction<'conn>;
 
-    fn begin(
+    #[allow(unused_variables)]
+    fn begin_with(
         &mut self,
+        options: TransactionOptions,
     ) -> impl Future<Output = Result<Self::Transaction<'_>, <Self::Connector as Connector>::Error>> + Send
     {
         async move {
@@ -169,16 +237,28 @@ impl Connection for AnyConn {
             match self {
                 #[cfg(feature = "sqlite")]
                 Self::Sqlite(conn) => conn
-                    .begin()
+                    .begin_with(options)
                     .await
                     .map(AnyTransaction::Sqlite)
                     .map_err(AnyError::Sqlite),
                 #[cfg(feature = "libsql")]
                 Self::Libsql(conn) => conn
-                    .begin()
+                    .begin_with(options)
                     .await
                     .map(AnyTransaction::LibSql)
                     .map_err(AnyError::LibSql),
+                #[cfg(feature = "postgres")]
+                Self::Postgres(conn) => conn
+                    .begin_with(options)
+                    .await
+                    .map(AnyTransaction::Postgres)
+                    .map_err(AnyError::Postgres),
+                #[cfg(feature = "mysql")]
+                Self::Mysql(conn) => conn
+                    .begin_with(options)
+                    .await
+                    .map(AnyTransaction::Mysql)
+                    .map_err(AnyError::Mysql),
                 _ => missing_db!(),
             }
         }
@@ -195,6 +275,10 @@ impl Executor for AnyConn {
             Self::Sqlite(info) => AnyInfo::Sqlite(info.db_info()),
             #[cfg(feature = "libsql")]
             Self::Libsql(info) => AnyInfo::Libsql(info.db_info()),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(info) => AnyInfo::Postgres(info.db_info()),
+            #[cfg(feature = "mysql")]
+            Self::Mysql(info) => AnyInfo::Mysql(info.db_info()),
             _ => missing_db!(),
         }
     }
@@ -225,6 +309,18 @@ impl Executor for AnyConn {
                     .await
                     .map(AnyStatement::LibSql)
                     .map_err(AnyError::LibSql),
+                #[cfg(feature = "postgres")]
+                Self::Postgres(conn) => conn
+                    .prepare(query)
+                    .await
+                    .map(AnyStatement::Postgres)
+                    .map_err(AnyError::Postgres),
+                #[cfg(feature = "mysql")]
+                Self::Mysql(conn) => conn
+                    .prepare(query)
+                    .await
+                    .map(AnyStatement::Mysql)
+                    .map_err(AnyError::Mysql),
                 _ => missing_db!(),
             }
         }
@@ -256,6 +352,24 @@ impl Executor for AnyConn {
                     stream: <LibSqlConn as Executor>::query(libsql, stmt, params),
                 })
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(postgres) => {
+                let AnyStatement::Postgres(stmt) = stmt else {
+                    panic!("Statement mismatch")
+                };
+                Box::pin(AnyQueryStream::<Postgres> {
+                    stream: <PostgresConn as Executor>::query(postgres, stmt, params),
+                })
+            }
+            #[cfg(feature = "mysql")]
+            Self::Mysql(mysql) => {
+                let AnyStatement::Mysql(stmt) = stmt else {
+                    panic!("Statement mismatch")
+                };
+                Box::pin(AnyQueryStream::<Mysql> {
+                    stream: <MysqlConn as Executor>::query(mysql, stmt, params),
+                })
+            }
             _ => missing_db!(),
         }
     }
@@ -265,7 +379,8 @@ impl Executor for AnyConn {
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: Vec<ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
         async move {
             #[allow(unreachable_patterns, irrefutable_let_patterns)]
             match self {
@@ -287,6 +402,24 @@ impl Executor for AnyConn {
                         .await
                         .map_err(Into::into)
                 }
+                #[cfg(feature = "postgres")]
+                Self::Postgres(postgres) => {
+                    let AnyStatement::Postgres(stmt) = stmt else {
+                        panic!("Statement mismatch")
+                    };
+                    <PostgresConn as Executor>::exec(postgres, stmt, params)
+                        .await
+                        .map_err(Into::into)
+                }
+                #[cfg(feature = "mysql")]
+                Self::Mysql(mysql) => {
+                    let AnyStatement::Mysql(stmt) = stmt else {
+                        panic!("Statement mismatch")
+                    };
+                    <MysqlConn as Executor>::exec(mysql, stmt, params)
+                        .await
+                        .map_err(Into::into)
+                }
                 _ => missing_db!(),
             }
         }
@@ -308,6 +441,14 @@ impl Executor for AnyConn {
                 Self::Libsql(libsql) => <LibSqlConn as Executor>::exec_batch(libsql, stmt)
                     .await
                     .map_err(Into::into),
+                #[cfg(feature = "postgres")]
+                Self::Postgres(postgres) => <PostgresConn as Executor>::exec_batch(postgres, stmt)
+                    .await
+                    .map_err(Into::into),
+                #[cfg(feature = "mysql")]
+                Self::Mysql(mysql) => <MysqlConn as Executor>::exec_batch(mysql, stmt)
+                    .await
+                    .map_err(Into::into),
                 _ => missing_db!(),
             }
         }
@@ -320,6 +461,10 @@ pub enum AnyRow {
     Sqlite(SqliteRow),
     #[cfg(feature = "libsql")]
     Libsql(LibSqlRow),
+    #[cfg(feature = "postgres")]
+    Postgres(PostgresRow),
+    #[cfg(feature = "mysql")]
+    Mysql(MysqlRow),
 }
 
 #[allow(unused_variables, unreachable_patterns)]
@@ -335,6 +480,12 @@ impl Row for AnyRow {
             AnyRow::Sqlite(row) => <SqliteRow as Row>::get(row, index).map_err(AnyError::Sqlite),
             #[cfg(feature = "libsql")]
             AnyRow::Libsql(row) => <LibSqlRow as Row>::get(row, index).map_err(AnyError::LibSql),
+            #[cfg(feature = "postgres")]
+            AnyRow::Postgres(row) => {
+                <PostgresRow as Row>::get(row, index).map_err(AnyError::Postgres)
+            }
+            #[cfg(feature = "mysql")]
+            AnyRow::Mysql(row) => <MysqlRow as Row>::get(row, index).map_err(AnyError::Mysql),
             _ => missing_db!(),
         }
     }
@@ -353,6 +504,14 @@ impl Row for AnyRow {
             AnyRow::Libsql(row) => {
                 <LibSqlRow as Row>::get_typed(row, index, ty).map_err(AnyError::LibSql)
             }
+            #[cfg(feature = "postgres")]
+            AnyRow::Postgres(row) => {
+                <PostgresRow as Row>::get_typed(row, index, ty).map_err(AnyError::Postgres)
+            }
+            #[cfg(feature = "mysql")]
+            AnyRow::Mysql(row) => {
+                <MysqlRow as Row>::get_typed(row, index, ty).map_err(AnyError::Mysql)
+            }
             _ => missing_db!(),
         }
     }
@@ -364,6 +523,10 @@ impl Row for AnyRow {
             AnyRow::Sqlite(row) => row.len(),
             #[cfg(feature = "libsql")]
             AnyRow::Libsql(row) => row.len(),
+            #[cfg(feature = "postgres")]
+            AnyRow::Postgres(row) => row.len(),
+            #[cfg(feature = "mysql")]
+            AnyRow::Mysql(row) => row.len(),
             _ => missing_db!(),
         }
     }
@@ -376,6 +539,10 @@ impl Row for AnyRow {
             AnyRow::Sqlite(row) => row.column_name(idx),
             #[cfg(feature = "libsql")]
             AnyRow::Libsql(row) => <LibSqlRow as Row>::column_name(row, idx),
+            #[cfg(feature = "postgres")]
+            AnyRow::Postgres(row) => <PostgresRow as Row>::column_name(row, idx),
+            #[cfg(feature = "mysql")]
+            AnyRow::Mysql(row) => <MysqlRow as Row>::column_name(row, idx),
             _ => missing_db!(),
         }
     }
@@ -395,12 +562,30 @@ impl From<LibSqlRow> for AnyRow {
     }
 }
 
+#[cfg(feature = "postgres")]
+impl From<PostgresRow> for AnyRow {
+    fn from(value: PostgresRow) -> Self {
+        Self::Postgres(value)
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl From<MysqlRow> for AnyRow {
+    fn from(value: MysqlRow) -> Self {
+        Self::Mysql(value)
+    }
+}
+
 #[non_exhaustive]
 pub enum AnyStatement {
     #[cfg(feature = "sqlite")]
     Sqlite(SqliteStatement),
     #[cfg(feature = "libsql")]
     LibSql(LibSqlStatement),
+    #[cfg(feature = "postgres")]
+    Postgres(PostgresStatement),
+    #[cfg(feature = "mysql")]
+    Mysql(MysqlStatement),
 }
 
 impl Statement for AnyStatement {
@@ -413,6 +598,10 @@ impl Statement for AnyStatement {
             Self::Sqlite(stmt) => stmt.finalize().map_err(AnyError::Sqlite),
             #[cfg(feature = "libsql")]
             Self::LibSql(stmt) => stmt.finalize().map_err(AnyError::LibSql),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(stmt) => stmt.finalize().map_err(AnyError::Postgres),
+            #[cfg(feature = "mysql")]
+            Self::Mysql(stmt) => stmt.finalize().map_err(AnyError::Mysql),
             _ => missing_db!(),
         }
     }
@@ -423,14 +612,58 @@ pub enum AnyTransaction<'conn> {
     #[cfg(feature = "sqlite")]
     Sqlite(SqliteTransaction<'conn>),
     #[cfg(feature = "libsql")]
-    LibSql(LibSqlTransaction),
+    LibSql(LibSqlTransaction<'conn>),
     #[cfg(feature = "postgres")]
-    Postgres(PgTrans<'conn>),
-    #[cfg(all(not(feature = "sqlite"), not(feature = "postgres")))]
+    Postgres(PostgresTransaction<'conn>),
+    #[cfg(feature = "mysql")]
+    Mysql(MysqlTransaction<'conn>),
+    #[cfg(all(
+        not(feature = "sqlite"),
+        not(feature = "libsql"),
+        not(feature = "postgres"),
+        not(feature = "mysql")
+    ))]
     Invariant(core::marker::PhantomData<&'conn ()>),
 }
 
 impl<'conn> Transaction<'conn> for AnyTransaction<'conn> {
+    type Savepoint<'a>
+        = AnyTransaction<'a>
+    where
+        Self: 'a;
+
+    fn savepoint(
+        &mut self,
+    ) -> impl Future<Output = Result<Self::Savepoint<'_>, <Self::Connector as Connector>::Error>> + Send
+    {
+        async move {
+            #[allow(unreachable_patterns)]
+            match self {
+                #[cfg(feature = "sqlite")]
+                Self::Sqlite(tx) => <SqliteTransaction as Transaction>::savepoint(tx)
+                    .await
+                    .map(AnyTransaction::Sqlite)
+                    .map_err(Into::into),
+                #[cfg(feature = "libsql")]
+                Self::LibSql(tx) => <LibSqlTransaction as Transaction>::savepoint(tx)
+                    .await
+                    .map(AnyTransaction::LibSql)
+                    .map_err(Into::into),
+                #[cfg(feature = "postgres")]
+                Self::Postgres(tx) => <PostgresTransaction as Transaction>::savepoint(tx)
+                    .await
+                    .map(AnyTransaction::Postgres)
+                    .map_err(Into::into),
+                #[cfg(feature = "mysql")]
+                Self::Mysql(tx) => <MysqlTransaction as Transaction>::savepoint(tx)
+                    .await
+                    .map(AnyTransaction::Mysql)
+                    .map_err(Into::into),
+                _ => missing_db!(),
+            }
+        }
+    }
+
     fn commit(
         self,
     ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send {
@@ -445,6 +678,14 @@ impl<'conn> Transaction<'conn> for AnyTransaction<'conn> {
                 Self::LibSql(tx) => <LibSqlTransaction as Transaction>::commit(tx)
                     .await
                     .map_err(Into::into),
+                #[cfg(feature = "postgres")]
+                Self::Postgres(tx) => <PostgresTransaction as Transaction>::commit(tx)
+                    .await
+                    .map_err(Into::into),
+                #[cfg(feature = "mysql")]
+                Self::Mysql(tx) => <MysqlTransaction as Transaction>::commit(tx)
+                    .await
+                    .map_err(Into::into),
                 _ => missing_db!(),
             }
         }
@@ -464,6 +705,14 @@ impl<'conn> Transaction<'conn> for AnyTransaction<'conn> {
                 Self::LibSql(tx) => <LibSqlTransaction as Transaction>::rollback(tx)
                     .await
                     .map_err(Into::into),
+                #[cfg(feature = "postgres")]
+                Self::Postgres(tx) => <PostgresTransaction as Transaction>::rollback(tx)
+                    .await
+                    .map_err(Into::into),
+                #[cfg(feature = "mysql")]
+                Self::Mysql(tx) => <MysqlTransaction as Transaction>::rollback(tx)
+                    .await
+                    .map_err(Into::into),
                 _ => missing_db!(),
             }
         }
@@ -480,6 +729,10 @@ impl Executor for AnyTransaction<'_> {
             Self::Sqlite(info) => AnyInfo::Sqlite(info.db_info()),
             #[cfg(feature = "libsql")]
             Self::LibSql(info) => AnyInfo::Libsql(info.db_info()),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(info) => AnyInfo::Postgres(info.db_info()),
+            #[cfg(feature = "mysql")]
+            Self::Mysql(info) => AnyInfo::Mysql(info.db_info()),
             _ => missing_db!(),
         }
     }
@@ -508,6 +761,18 @@ impl Executor for AnyTransaction<'_> {
                     .await
                     .map(AnyStatement::LibSql)
                     .map_err(AnyError::LibSql),
+                #[cfg(feature = "postgres")]
+                Self::Postgres(conn) => conn
+                    .prepare(query)
+                    .await
+                    .map(AnyStatement::Postgres)
+                    .map_err(AnyError::Postgres),
+                #[cfg(feature = "mysql")]
+                Self::Mysql(conn) => conn
+                    .prepare(query)
+                    .await
+                    .map(AnyStatement::Mysql)
+                    .map_err(AnyError::Mysql),
                 _ => missing_db!(),
             }
         }
@@ -537,6 +802,24 @@ impl Executor for AnyTransaction<'_> {
                     stream: <LibSqlTransaction as Executor>::query(libsql, stmt, params),
                 })
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(postgres) => {
+                let AnyStatement::Postgres(stmt) = stmt else {
+                    panic!("Statement mismatch")
+                };
+                Box::pin(AnyQueryStream::<Postgres> {
+                    stream: <PostgresTransaction as Executor>::query(postgres, stmt, params),
+                })
+            }
+            #[cfg(feature = "mysql")]
+            Self::Mysql(mysql) => {
+                let AnyStatement::Mysql(stmt) = stmt else {
+                    panic!("Statement mismatch")
+                };
+                Box::pin(AnyQueryStream::<Mysql> {
+                    stream: <MysqlTransaction as Executor>::query(mysql, stmt, params),
+                })
+            }
             _ => missing_db!(),
         }
     }
@@ -545,7 +828,8 @@ impl Executor for AnyTransaction<'_> {
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: Vec<ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
         async move {
             #[allow(unreachable_patterns, irrefutable_let_patterns)]
             match self {
@@ -567,6 +851,24 @@ impl Executor for AnyTransaction<'_> {
                         .await
                         .map_err(Into::into)
                 }
+                #[cfg(feature = "postgres")]
+                Self::Postgres(postgres) => {
+                    let AnyStatement::Postgres(stmt) = stmt else {
+                        panic!("Statement mismatch")
+                    };
+                    <PostgresTransaction as Executor>::exec(postgres, stmt, params)
+                        .await
+                        .map_err(Into::into)
+                }
+                #[cfg(feature = "mysql")]
+                Self::Mysql(mysql) => {
+                    let AnyStatement::Mysql(stmt) = stmt else {
+                        panic!("Statement mismatch")
+                    };
+                    <MysqlTransaction as Executor>::exec(mysql, stmt, params)
+                        .await
+                        .map_err(Into::into)
+                }
                 _ => missing_db!(),
             }
         }
@@ -587,6 +889,16 @@ impl Executor for AnyTransaction<'_> {
                 Self::LibSql(libsql) => <LibSqlTransaction as Executor>::exec_batch(libsql, stmt)
                     .await
                     .map_err(Into::into),
+                #[cfg(feature = "postgres")]
+                Self::Postgres(postgres) => {
+                    <PostgresTransaction as Executor>::exec_batch(postgres, stmt)
+                        .await
+                        .map_err(Into::into)
+                }
+                #[cfg(feature = "mysql")]
+                Self::Mysql(mysql) => <MysqlTransaction as Executor>::exec_batch(mysql, stmt)
+                    .await
+                    .map_err(Into::into),
                 _ => missing_db!(),
             }
         }
@@ -600,6 +912,10 @@ pub enum AnyError {
     Sqlite(SqliteError),
     #[cfg(feature = "libsql")]
     LibSql(LibSqlError),
+    #[cfg(feature = "postgres")]
+    Postgres(PostgresError),
+    #[cfg(feature = "mysql")]
+    Mysql(MysqlError),
     Message(&'static str),
 }
 
@@ -612,6 +928,10 @@ impl core::fmt::Display for AnyError {
             AnyError::Sqlite(err) => write!(f, "{}", err),
             #[cfg(feature = "libsql")]
             AnyError::LibSql(err) => write!(f, "{}", err),
+            #[cfg(feature = "postgres")]
+            AnyError::Postgres(err) => write!(f, "{}", err),
+            #[cfg(feature = "mysql")]
+            AnyError::Mysql(err) => write!(f, "{}", err),
             AnyError::Message(msg) => msg.fmt(f),
             _ => missing_db!(),
         }
@@ -626,6 +946,57 @@ impl core::error::Error for AnyError {
             AnyError::Sqlite(err) => Some(err),
             #[cfg(feature = "libsql")]
             AnyError::LibSql(err) => Some(err),
+            #[cfg(feature = "postgres")]
+            AnyError::Postgres(err) => Some(err),
+            #[cfg(feature = "mysql")]
+            AnyError::Mysql(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl DatabaseError for AnyError {
+    fn kind(&self) -> ErrorKind {
+        #[allow(unreachable_patterns)]
+        match self {
+            #[cfg(feature = "sqlite")]
+            AnyError::Sqlite(err) => err.kind(),
+            #[cfg(feature = "libsql")]
+            AnyError::LibSql(err) => err.kind(),
+            #[cfg(feature = "postgres")]
+            AnyError::Postgres(err) => err.kind(),
+            #[cfg(feature = "mysql")]
+            AnyError::Mysql(err) => err.kind(),
+            _ => ErrorKind::Other,
+        }
+    }
+
+    fn constraint(&self) -> Option<&str> {
+        #[allow(unreachable_patterns)]
+        match self {
+            #[cfg(feature = "sqlite")]
+            AnyError::Sqlite(err) => err.constraint(),
+            #[cfg(feature = "libsql")]
+            AnyError::LibSql(err) => err.constraint(),
+            #[cfg(feature = "postgres")]
+            AnyError::Postgres(err) => err.constraint(),
+            #[cfg(feature = "mysql")]
+            AnyError::Mysql(err) => err.constraint(),
+            _ => None,
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        #[allow(unreachable_patterns)]
+        match self {
+            #[cfg(feature = "sqlite")]
+            AnyError::Sqlite(err) => err.table(),
+            #[cfg(feature = "libsql")]
+            AnyError::LibSql(err) => err.table(),
+            #[cfg(feature = "postgres")]
+            AnyError::Postgres(err) => err.table(),
+            #[cfg(feature = "mysql")]
+            AnyError::Mysql(err) => err.table(),
             _ => None,
         }
     }
@@ -645,6 +1016,20 @@ impl From<LibSqlError> for AnyError {
     }
 }
 
+#[cfg(feature = "postgres")]
+impl From<PostgresError> for AnyError {
+    fn from(value: PostgresError) -> Self {
+        Self::Postgres(value)
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl From<MysqlError> for AnyError {
+    fn from(value: MysqlError) -> Self {
+        Self::Mysql(value)
+    }
+}
+
 pin_project! {
     struct AnyQueryStream<'a, T: Connector> {
         #[pin]
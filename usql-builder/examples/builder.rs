@@ -1,7 +1,7 @@
 use usql_builder::{
     StatementExt,
     expr::*,
-    mutate::{Returning, Set, insert, update},
+    mutate::{Set, insert, update},
     schema::{Column, ColumnType, CreateIndex, create_table},
     select::{
         FilterQuery, GroupQuery, IdentExt, Join, JoinQuery, LimitQuery, Order, QueryExt, SortQuery,
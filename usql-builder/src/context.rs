@@ -27,6 +27,12 @@ impl<'a> Context<'a> {
     pub fn dialect(&self) -> System {
         self.system
     }
+
+    /// MySQL has no RETURNING clause
+    pub fn supports_returning(&self) -> bool {
+        !matches!(self.system, System::Mysql)
+    }
+
     pub fn push<S: Into<ValueCow<'a>>>(&mut self, value: S) -> Result<(), Error> {
         let value = value.into();
 
@@ -35,7 +41,7 @@ impl<'a> Context<'a> {
             match self.system {
                 System::Mysql | System::LibSql | System::Sqlite => self.write_str("?"),
                 System::Postgres => {
-                    write!(self.output, "${}", self.output.len())
+                    write!(self.output, "${}", self.bindings.len())
                 }
             }?;
         } else {
@@ -1,12 +1,21 @@
 use core::fmt;
 
 use alloc::string::String;
+use usql_core::System;
 
 #[derive(Debug)]
 pub enum Error {
     Write(fmt::Error),
     InvalidAutoType(String),
-    InvalidValueCount { expected: usize, found: usize },
+    InvalidValueCount {
+        expected: usize,
+        found: usize,
+    },
+    /// The statement can't be expressed in the dialect
+    Unsupported {
+        dialect: System,
+        feature: &'static str,
+    },
 }
 
 impl From<fmt::Error> for Error {
@@ -27,6 +36,9 @@ impl fmt::Display for Error {
                     expected, found
                 )
             }
+            Self::Unsupported { dialect, feature } => {
+                write!(f, "{} is not supported by {:?}", feature, dialect)
+            }
         }
     }
 }
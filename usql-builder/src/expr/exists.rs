@@ -0,0 +1,78 @@
+use crate::{Context, Error, expr::Expression, select::Query};
+use core::fmt::Write;
+
+pub fn exists<Q>(query: Q) -> Exists<Q> {
+    Exists::new(query, false)
+}
+
+pub fn not_exists<Q>(query: Q) -> Exists<Q> {
+    Exists::new(query, true)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Exists<Q> {
+    query: Q,
+    negated: bool,
+}
+
+impl<Q> Exists<Q> {
+    pub fn new(query: Q, negated: bool) -> Exists<Q> {
+        Exists { query, negated }
+    }
+}
+
+impl<'a, Q> Expression<'a> for Exists<Q>
+where
+    Q: Query<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        if self.negated {
+            ctx.write_str("NOT ")?;
+        }
+        ctx.write_str("EXISTS (")?;
+        self.query.build(ctx)?;
+        ctx.write_char(')')?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use usql_core::System;
+
+    use super::*;
+    use crate::{
+        expr::{ExpressionExt, val},
+        select::{FilterQuery, Join, JoinQuery, QueryExt, Select, TargetExt},
+    };
+
+    #[test]
+    fn test_subquery_build() {
+        let query = Select::new("posts", "title")
+            .join(
+                Join::inner(
+                    Select::new("users", "id")
+                        .filter("active".eql(val(true)))
+                        .alias("u"),
+                )
+                .on("u".col("id").eql("posts".col("user_id"))),
+            )
+            .filter(
+                "posts"
+                    .col("id")
+                    .contains(Select::new("tags", "post_id").filter("name".eql(val("rust"))))
+                    .and(not_exists(
+                        Select::new("flags", "id").filter("flags".col("post_id").eql(val(3))),
+                    )),
+            );
+
+        let mut ctx = Context::new(System::Postgres);
+        Query::build(query, &mut ctx).expect("build");
+
+        assert_eq!(
+            ctx.to_string(),
+            r#"SELECT "title" FROM "posts" INNER JOIN (SELECT "id" FROM "users" WHERE "active" = $1) AS "u" ON "u"."id" = "posts"."user_id" WHERE "posts"."id" IN (SELECT "post_id" FROM "tags" WHERE "name" = $2) AND NOT EXISTS (SELECT "id" FROM "flags" WHERE "flags"."post_id" = $3)"#
+        );
+    }
+}
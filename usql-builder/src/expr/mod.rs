@@ -10,11 +10,15 @@ mod between;
 mod binary;
 mod call;
 mod case;
+mod exists;
 mod ext;
 mod ident;
 mod value;
+mod window;
 
-pub use self::{binary::*, call::*, case::*, ext::*, ident::Ident, value::val};
+pub use self::{
+    binary::*, call::*, case::*, exists::*, ext::*, ident::Ident, value::val, window::*,
+};
 
 pub trait Expression<'a> {
     fn build(self, ctx: &mut Context<'a>) -> Result<(), Error>;
@@ -0,0 +1,254 @@
+use core::fmt::Write;
+
+use usql_core::System;
+
+use crate::{
+    Context, Error,
+    expr::{Args, Call, Expression, Ident},
+    select::SortKey,
+};
+
+pub fn row_number() -> Call<&'static str, ()> {
+    Call::new("ROW_NUMBER", ())
+}
+
+pub fn rank() -> Call<&'static str, ()> {
+    Call::new("RANK", ())
+}
+
+pub fn dense_rank() -> Call<&'static str, ()> {
+    Call::new("DENSE_RANK", ())
+}
+
+pub fn lag<E>(expr: E) -> Call<&'static str, (E,)> {
+    Call::new("LAG", (expr,))
+}
+
+pub fn lead<E>(expr: E) -> Call<&'static str, (E,)> {
+    Call::new("LEAD", (expr,))
+}
+
+impl<T, A> Call<T, A> {
+    /// Evaluate the function over a window, either a `Window`
+    /// or the name of one defined on the select
+    pub fn over<W>(self, window: W) -> Over<Self, W> {
+        Over::new(self, window)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Over<F, W> {
+    func: F,
+    window: W,
+}
+
+impl<F, W> Over<F, W> {
+    pub fn new(func: F, window: W) -> Over<F, W> {
+        Over { func, window }
+    }
+}
+
+impl<'a, F, W> Expression<'a> for Over<F, W>
+where
+    F: Expression<'a>,
+    W: WindowSpec<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        self.func.build(ctx)?;
+        ctx.write_str(" OVER ")?;
+        self.window.build(ctx)?;
+        Ok(())
+    }
+}
+
+impl<'a, F, W> Ident<'a> for Over<F, W>
+where
+    F: Expression<'a>,
+    W: WindowSpec<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        <Self as Expression<'a>>::build(self, ctx)
+    }
+}
+
+pub trait WindowSpec<'a> {
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error>;
+}
+
+impl<'a, 'b> WindowSpec<'a> for &'b str {
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        ctx.push_identifier(self)
+    }
+}
+
+impl<'a, P, O> WindowSpec<'a> for Window<P, O>
+where
+    P: Args<'a>,
+    O: SortKey<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        ctx.write_char('(')?;
+        self.build_definition(ctx)?;
+        ctx.write_char(')')?;
+        Ok(())
+    }
+}
+
+pub fn window() -> Window<(), ()> {
+    Window::new()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Window<P, O> {
+    partition: Option<P>,
+    order: Option<O>,
+    frame: Option<Frame>,
+}
+
+impl Window<(), ()> {
+    pub fn new() -> Window<(), ()> {
+        Window {
+            partition: None,
+            order: None,
+            frame: None,
+        }
+    }
+}
+
+impl Default for Window<(), ()> {
+    fn default() -> Self {
+        Window::new()
+    }
+}
+
+impl<P, O> Window<P, O> {
+    pub fn partition_by<T>(self, partition: T) -> Window<T, O> {
+        Window {
+            partition: Some(partition),
+            order: self.order,
+            frame: self.frame,
+        }
+    }
+
+    pub fn order_by<T>(self, order: T) -> Window<P, T> {
+        Window {
+            partition: self.partition,
+            order: Some(order),
+            frame: self.frame,
+        }
+    }
+
+    pub fn rows(self, start: FrameBound, end: impl Into<Option<FrameBound>>) -> Self {
+        self.frame(Frame::new(FrameUnit::Rows, start, end))
+    }
+
+    pub fn range(self, start: FrameBound, end: impl Into<Option<FrameBound>>) -> Self {
+        self.frame(Frame::new(FrameUnit::Range, start, end))
+    }
+
+    /// Not supported by MySQL
+    pub fn groups(self, start: FrameBound, end: impl Into<Option<FrameBound>>) -> Self {
+        self.frame(Frame::new(FrameUnit::Groups, start, end))
+    }
+
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+}
+
+impl<'a, P, O> Window<P, O>
+where
+    P: Args<'a>,
+    O: SortKey<'a>,
+{
+    /// Write the definition without the enclosing parentheses
+    pub(crate) fn build_definition(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        let mut sep = "";
+
+        if let Some(partition) = self.partition {
+            ctx.write_str("PARTITION BY ")?;
+            partition.build(ctx, ", ")?;
+            sep = " ";
+        }
+
+        if let Some(order) = self.order {
+            write!(ctx, "{sep}ORDER BY ")?;
+            order.build(ctx)?;
+            sep = " ";
+        }
+
+        if let Some(frame) = self.frame {
+            ctx.write_str(sep)?;
+            frame.build(ctx)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FrameUnit {
+    Rows,
+    Range,
+    Groups,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
+impl core::fmt::Display for FrameBound {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FrameBound::UnboundedPreceding => f.write_str("UNBOUNDED PRECEDING"),
+            FrameBound::Preceding(n) => write!(f, "{n} PRECEDING"),
+            FrameBound::CurrentRow => f.write_str("CURRENT ROW"),
+            FrameBound::Following(n) => write!(f, "{n} FOLLOWING"),
+            FrameBound::UnboundedFollowing => f.write_str("UNBOUNDED FOLLOWING"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Frame {
+    unit: FrameUnit,
+    start: FrameBound,
+    end: Option<FrameBound>,
+}
+
+impl Frame {
+    pub fn new(unit: FrameUnit, start: FrameBound, end: impl Into<Option<FrameBound>>) -> Frame {
+        Frame {
+            unit,
+            start,
+            end: end.into(),
+        }
+    }
+
+    fn build(self, ctx: &mut Context<'_>) -> Result<(), Error> {
+        match self.unit {
+            FrameUnit::Rows => ctx.write_str("ROWS")?,
+            FrameUnit::Range => ctx.write_str("RANGE")?,
+            FrameUnit::Groups if ctx.dialect() == System::Mysql => {
+                return Err(Error::Unsupported {
+                    dialect: System::Mysql,
+                    feature: "GROUPS window frame",
+                });
+            }
+            FrameUnit::Groups => ctx.write_str("GROUPS")?,
+        }
+
+        match self.end {
+            Some(end) => write!(ctx, " BETWEEN {} AND {}", self.start, end)?,
+            None => write!(ctx, " {}", self.start)?,
+        }
+
+        Ok(())
+    }
+}
@@ -2,8 +2,16 @@ use core::fmt::Write;
 
 use alloc::borrow::Cow;
 
+use usql_core::System;
+
 use crate::{
+    Error,
     expr::{Expression, ExpressionBox, expr_box},
+    mutate::{
+        join::{build_join, build_join_table, build_join_where},
+        set::{Returning, ReturningStmt},
+    },
+    select::{JoinOn, Selection, Target},
     statement::Statement,
 };
 
@@ -27,8 +35,28 @@ impl<'a> Delete<'a> {
         self.filter = Some(expr_box(filter));
         self
     }
+
+    /// Return the deleted rows, fails to build on MySQL unless a fallback is set
+    pub fn returning<S>(self, selection: S) -> ReturningStmt<S, Self>
+    where
+        S: Selection<'a>,
+    {
+        <Self as Returning<'a>>::returning(self, selection)
+    }
+
+    /// Delete the rows matching a joined table.
+    ///
+    /// Renders as `DELETE .. USING` on Postgres, which only supports inner joins,
+    /// and as a multi-table delete on MySQL. SQLite has no equivalent.
+    pub fn join<T, E>(self, join: JoinOn<T, E>) -> DeleteJoin<'a, T, E> {
+        DeleteJoin { delete: self, join }
+    }
 }
 
+impl<'a> Returning<'a> for Delete<'a> {}
+
+impl<'a, T, E> Returning<'a> for DeleteJoin<'a, T, E> {}
+
 impl<'a> Statement<'a> for Delete<'a> {
     fn build(self, ctx: &mut crate::Context<'a>) -> Result<(), crate::Error> {
         ctx.write_str("DELETE FROM ")?;
@@ -42,3 +70,54 @@ impl<'a> Statement<'a> for Delete<'a> {
         Ok(())
     }
 }
+
+pub struct DeleteJoin<'a, T, E> {
+    delete: Delete<'a>,
+    join: JoinOn<T, E>,
+}
+
+impl<'a, T, E> DeleteJoin<'a, T, E> {
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Expression<'a> + Send + Sync + Clone + 'a,
+    {
+        self.delete = self.delete.filter(filter);
+        self
+    }
+}
+
+impl<'a, T, E> Statement<'a> for DeleteJoin<'a, T, E>
+where
+    T: Target<'a>,
+    E: Expression<'a>,
+{
+    fn build(self, ctx: &mut crate::Context<'a>) -> Result<(), crate::Error> {
+        let Delete { table, filter } = self.delete;
+
+        match ctx.dialect() {
+            System::Mysql => {
+                ctx.write_str("DELETE ")?;
+                ctx.push_identifier(&table)?;
+                ctx.write_str(" FROM ")?;
+                ctx.push_identifier(&table)?;
+                build_join(self.join, ctx)?;
+                if let Some(filter) = filter {
+                    ctx.write_str(" WHERE ")?;
+                    filter.build(ctx)?;
+                }
+                Ok(())
+            }
+            System::Postgres => {
+                ctx.write_str("DELETE FROM ")?;
+                ctx.push_identifier(&table)?;
+                ctx.write_str(" USING ")?;
+                let on = build_join_table(self.join, ctx, "DELETE .. USING with an outer join")?;
+                build_join_where(on, filter, ctx)
+            }
+            dialect @ (System::Sqlite | System::LibSql) => Err(Error::Unsupported {
+                dialect,
+                feature: "DELETE with a join",
+            }),
+        }
+    }
+}
@@ -5,7 +5,10 @@ use alloc::{borrow::Cow, vec::Vec};
 use crate::{
     Context, Error,
     expr::{ExpressionBox, expr_box},
-    mutate::set::Set,
+    mutate::{
+        OnConflict,
+        set::{ReturningFallback, Set, build_returning},
+    },
     select::Selection,
     statement::Statement,
 };
@@ -46,6 +49,16 @@ impl<'key, 'val> Insert<'key, 'val> {
         }
     }
 
+    /// Resolve conflicts on the unique `columns`, finished with
+    /// `do_nothing` or `do_update`
+    pub fn on_conflict<I, C>(self, columns: I) -> OnConflict<'key, Self>
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Cow<'key, str>>,
+    {
+        OnConflict::new(self, columns)
+    }
+
     pub fn returning<T>(self, selection: T) -> InsertReturning<'key, 'val, T>
     where
         T: Selection<'val>,
@@ -53,6 +66,7 @@ impl<'key, 'val> Insert<'key, 'val> {
         InsertReturning {
             insert: self,
             returning: selection,
+            fallback: ReturningFallback::Error,
         }
     }
 }
@@ -87,6 +101,14 @@ impl<'key, 'val> Statement<'val> for Insert<'key, 'val> {
 pub struct InsertReturning<'key, 'val, S> {
     insert: Insert<'key, 'val>,
     returning: S,
+    fallback: ReturningFallback,
+}
+
+impl<'key, 'val, S> InsertReturning<'key, 'val, S> {
+    pub fn fallback(mut self, fallback: ReturningFallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
 }
 
 impl<'key, 'val, S> Set<'key, 'val> for InsertReturning<'key, 'val, S>
@@ -113,8 +135,6 @@ where
 {
     fn build(self, ctx: &mut Context<'val>) -> Result<(), Error> {
         <Insert<'key, 'val> as Statement>::build(self.insert, ctx)?;
-        write!(ctx, " RETURNING ")?;
-        self.returning.build(ctx)?;
-        Ok(())
+        build_returning(self.returning, self.fallback, ctx)
     }
 }
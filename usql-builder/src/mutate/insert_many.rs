@@ -1,11 +1,12 @@
 use core::fmt::Write;
 
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, vec::Vec};
 use usql_value::ValueCow;
 
 use crate::{
     Context, Error,
     expr::{Expression, Ident},
+    mutate::OnConflict,
     statement::Statement,
 };
 
@@ -113,6 +114,16 @@ impl<'val, T, K: Fields<'val>> InsertMany<'val, T, K> {
 
         Ok(self)
     }
+
+    /// Resolve conflicts on the unique `columns`, finished with
+    /// `do_nothing` or `do_update`
+    pub fn on_conflict<'key, I, C>(self, columns: I) -> OnConflict<'key, Self>
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Cow<'key, str>>,
+    {
+        OnConflict::new(self, columns)
+    }
 }
 
 impl<'val, T: Ident<'val>, K: Fields<'val>> Statement<'val> for InsertMany<'val, T, K> {
@@ -0,0 +1,107 @@
+use core::fmt::Write;
+
+use crate::{
+    Context, Error,
+    expr::{Expression, ExpressionBox},
+    select::{JoinOn, JoinType, Joinable, Target},
+};
+
+/// Write the joined table for dialects which list it in a FROM or USING clause,
+/// those can't express outer joins.
+pub(crate) fn build_join_table<'a, T, E>(
+    join: JoinOn<T, E>,
+    ctx: &mut Context<'a>,
+    feature: &'static str,
+) -> Result<E, Error>
+where
+    T: Target<'a>,
+{
+    if join.join.kind != JoinType::Inner {
+        return Err(Error::Unsupported {
+            dialect: ctx.dialect(),
+            feature,
+        });
+    }
+
+    join.join.table.build(ctx)?;
+    Ok(join.on)
+}
+
+/// Write the join condition and the filter as a single WHERE clause
+pub(crate) fn build_join_where<'a, E>(
+    on: E,
+    filter: Option<ExpressionBox<'a>>,
+    ctx: &mut Context<'a>,
+) -> Result<(), Error>
+where
+    E: Expression<'a>,
+{
+    ctx.write_str(" WHERE ")?;
+    match filter {
+        Some(filter) => {
+            ctx.write_char('(')?;
+            on.build(ctx)?;
+            ctx.write_str(") AND (")?;
+            filter.build(ctx)?;
+            ctx.write_char(')')?;
+        }
+        None => on.build(ctx)?,
+    }
+    Ok(())
+}
+
+/// Write the join as is, for MySQL's multi-table syntax
+pub(crate) fn build_join<'a, T, E>(join: JoinOn<T, E>, ctx: &mut Context<'a>) -> Result<(), Error>
+where
+    T: Target<'a>,
+    E: Expression<'a>,
+{
+    ctx.write_char(' ')?;
+    <JoinOn<T, E> as Joinable<'a>>::build(join, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use usql_core::System;
+
+    use crate::{
+        Error, StatementExt,
+        expr::{BinaryExpression, ExpressionExt, val},
+        mutate::{Delete, Set, Update},
+        select::{Join, JoinOn, TableColumn, TargetExt},
+    };
+
+    type Column = TableColumn<&'static str, &'static str>;
+
+    fn join() -> JoinOn<&'static str, BinaryExpression<Column, Column>> {
+        Join::inner("teams").on("teams".col("id").eql("users".col("team_id")))
+    }
+
+    #[test]
+    fn test_update_join_build() {
+        let update = || {
+            Update::new("users")
+                .join(join())
+                .filter("teams".col("name").eql(val("core")))
+                .with("active", val(true))
+        };
+
+        let stmt = update().to_sql(System::Postgres).expect("build");
+        assert_eq!(
+            stmt.sql,
+            r#"UPDATE "users" SET "active" = $1 FROM "teams" WHERE ("teams"."id" = "users"."team_id") AND ("teams"."name" = $2)"#
+        );
+
+        let stmt = update().to_sql(System::Mysql).expect("build");
+        assert_eq!(
+            stmt.sql,
+            "UPDATE `users` INNER JOIN `teams` ON `teams`.`id` = `users`.`team_id` SET `active` = ? WHERE `teams`.`name` = ?"
+        );
+    }
+
+    #[test]
+    fn test_delete_join_unsupported() {
+        let result = Delete::new("users").join(join()).to_sql(System::Sqlite);
+        assert!(matches!(result, Err(Error::Unsupported { .. })));
+    }
+}
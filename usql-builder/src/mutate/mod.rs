@@ -1,13 +1,16 @@
 mod delete;
 mod insert;
 mod insert_many;
+mod join;
 mod set;
 mod update;
+mod upsert;
 
 pub use self::{
     delete::*,
     insert::*,
     insert_many::*,
-    set::{Returning, ReturningStmt, Set},
+    set::{Returning, ReturningFallback, ReturningStmt, Set},
     update::*,
+    upsert::*,
 };
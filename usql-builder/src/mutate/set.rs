@@ -23,10 +23,51 @@ pub trait Set<'key, 'val> {
     fn build(self, ctx: &mut Context<'val>) -> Result<(), Error>;
 }
 
+/// What to do when the dialect has no RETURNING clause
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ReturningFallback {
+    /// Fail with `Error::Unsupported`
+    #[default]
+    Error,
+    /// Leave the clause out, the caller then relies on the affected rows
+    /// and last insert id of the query result instead
+    Omit,
+}
+
+pub(crate) fn build_returning<'val, S>(
+    selection: S,
+    fallback: ReturningFallback,
+    ctx: &mut Context<'val>,
+) -> Result<(), Error>
+where
+    S: Selection<'val>,
+{
+    if !ctx.supports_returning() {
+        return match fallback {
+            ReturningFallback::Error => Err(Error::Unsupported {
+                dialect: ctx.dialect(),
+                feature: "RETURNING",
+            }),
+            ReturningFallback::Omit => Ok(()),
+        };
+    }
+
+    write!(ctx, " RETURNING ")?;
+    selection.build(ctx)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ReturningStmt<S, T> {
     selection: S,
     stmt: T,
+    fallback: ReturningFallback,
+}
+
+impl<S, T> ReturningStmt<S, T> {
+    pub fn fallback(mut self, fallback: ReturningFallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
 }
 
 impl<'val, S, T> Statement<'val> for ReturningStmt<S, T>
@@ -36,12 +77,7 @@ where
 {
     fn build(self, ctx: &mut Context<'val>) -> Result<(), Error> {
         self.stmt.build(ctx)?;
-
-        write!(ctx, " RETURNING ")?;
-
-        self.selection.build(ctx)?;
-
-        Ok(())
+        build_returning(self.selection, self.fallback, ctx)
     }
 }
 
@@ -53,6 +89,7 @@ pub trait Returning<'val>: Sized {
         ReturningStmt {
             selection,
             stmt: self,
+            fallback: ReturningFallback::Error,
         }
     }
 }
@@ -63,3 +100,39 @@ where
     R: Statement<'val>,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use usql_core::System;
+
+    use super::*;
+    use crate::{
+        StatementExt,
+        expr::{ExpressionExt, val},
+        mutate::Delete,
+    };
+
+    #[test]
+    fn test_returning_fallback() {
+        let delete = || {
+            Delete::new("users")
+                .filter("id".eql(val(1)))
+                .returning("id")
+        };
+
+        let stmt = delete().to_sql(System::Sqlite).expect("build");
+        assert_eq!(
+            stmt.sql,
+            r#"DELETE FROM "users" WHERE "id" = ? RETURNING "id""#
+        );
+
+        let result = delete().to_sql(System::Mysql);
+        assert!(matches!(result, Err(Error::Unsupported { .. })));
+
+        let stmt = delete()
+            .fallback(ReturningFallback::Omit)
+            .to_sql(System::Mysql)
+            .expect("build");
+        assert_eq!(stmt.sql, "DELETE FROM `users` WHERE `id` = ?");
+    }
+}
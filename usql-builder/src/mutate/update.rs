@@ -1,9 +1,16 @@
 use alloc::{borrow::Cow, fmt::Write, vec::Vec};
 
+use usql_core::System;
+
 use crate::{
     Context, Error,
     expr::{Expression, ExpressionBox, expr_box},
-    mutate::{Set, set::Returning},
+    mutate::{
+        Set,
+        join::{build_join, build_join_table, build_join_where},
+        set::{Returning, ReturningStmt},
+    },
+    select::{JoinOn, Selection, Target},
     statement::Statement,
 };
 
@@ -44,6 +51,18 @@ impl<'key, 'val> Update<'key, 'val> {
             filter: expr,
         }
     }
+
+    /// Update the rows matching a joined table.
+    ///
+    /// Renders as `UPDATE .. FROM` on Postgres and SQLite (3.33+), which only
+    /// support inner joins, and as a multi-table update on MySQL.
+    pub fn join<T, E>(self, join: JoinOn<T, E>) -> UpdateJoin<'key, 'val, T, E> {
+        UpdateJoin {
+            update: self,
+            join,
+            filter: None,
+        }
+    }
 }
 
 impl<'key, 'val> Returning<'val> for Update<'key, 'val> {}
@@ -52,6 +71,12 @@ impl<'key, 'val> Statement<'val> for Update<'key, 'val> {
     fn build(self, ctx: &mut Context<'val>) -> Result<(), Error> {
         write!(ctx, "UPDATE ")?;
         ctx.push_identifier(&self.table)?;
+        self.build_set(ctx)
+    }
+}
+
+impl<'key, 'val> Update<'key, 'val> {
+    fn build_set(self, ctx: &mut Context<'val>) -> Result<(), Error> {
         write!(ctx, " SET ")?;
         for (idx, value) in self.keys.iter().enumerate() {
             if idx > 0 {
@@ -71,6 +96,16 @@ pub struct UpdateFilter<'key, 'val, E> {
     filter: E,
 }
 
+impl<'key, 'val, E> UpdateFilter<'key, 'val, E> {
+    /// Return the updated rows, fails to build on MySQL unless a fallback is set
+    pub fn returning<S>(self, selection: S) -> ReturningStmt<S, Self>
+    where
+        S: Selection<'val>,
+    {
+        <Self as Returning<'val>>::returning(self, selection)
+    }
+}
+
 impl<'key, 'val, E> Returning<'val> for UpdateFilter<'key, 'val, E> {}
 
 impl<'key, 'val, E> Set<'key, 'val> for UpdateFilter<'key, 'val, E>
@@ -103,6 +138,69 @@ where
     }
 }
 
+pub struct UpdateJoin<'key, 'val, T, E> {
+    update: Update<'key, 'val>,
+    join: JoinOn<T, E>,
+    filter: Option<ExpressionBox<'val>>,
+}
+
+impl<'key, 'val, T, E> UpdateJoin<'key, 'val, T, E> {
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Expression<'val> + Send + Sync + Clone + 'val,
+    {
+        self.filter = Some(expr_box(filter));
+        self
+    }
+}
+
+impl<'key, 'val, T, E> Returning<'val> for UpdateJoin<'key, 'val, T, E> {}
+
+impl<'key, 'val, T, E> Set<'key, 'val> for UpdateJoin<'key, 'val, T, E>
+where
+    T: Target<'val>,
+    E: Expression<'val>,
+{
+    fn set<F, V>(&mut self, field: F, value: V) -> &mut Self
+    where
+        F: Into<Cow<'key, str>>,
+        V: Expression<'val> + Send + Sync + Clone + 'val,
+    {
+        self.update.set(field, value);
+        self
+    }
+
+    fn build(self, ctx: &mut Context<'val>) -> Result<(), Error> {
+        <Self as Statement>::build(self, ctx)
+    }
+}
+
+impl<'key, 'val, T, E> Statement<'val> for UpdateJoin<'key, 'val, T, E>
+where
+    T: Target<'val>,
+    E: Expression<'val>,
+{
+    fn build(self, ctx: &mut Context<'val>) -> Result<(), Error> {
+        write!(ctx, "UPDATE ")?;
+        ctx.push_identifier(&self.update.table)?;
+
+        if ctx.dialect() == System::Mysql {
+            build_join(self.join, ctx)?;
+            self.update.build_set(ctx)?;
+            if let Some(filter) = self.filter {
+                write!(ctx, " WHERE ")?;
+                filter.build(ctx)?;
+            }
+            return Ok(());
+        }
+
+        self.update.build_set(ctx)?;
+        write!(ctx, " FROM ")?;
+        let on = build_join_table(self.join, ctx, "UPDATE .. FROM with an outer join")?;
+        build_join_where(on, self.filter, ctx)
+    }
+}
+
 pub fn update<'key, 'val>(table: impl Into<Cow<'key, str>>) -> Update<'key, 'val> {
     Update::new(table)
 }
@@ -0,0 +1,286 @@
+use core::fmt::Write;
+
+use alloc::{borrow::Cow, vec::Vec};
+use usql_core::System;
+
+use crate::{
+    Context, Error,
+    expr::{Expression, ExpressionBox, expr_box},
+    mutate::{Set, set::Returning},
+    statement::Statement,
+};
+
+/// Reference to the value a conflicting insert tried to write to `column`.
+///
+/// Renders as `excluded.column`, or `VALUES(column)` on MySQL.
+pub fn excluded<'key>(column: impl Into<Cow<'key, str>>) -> Excluded<'key> {
+    Excluded {
+        column: column.into(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Excluded<'key> {
+    column: Cow<'key, str>,
+}
+
+impl<'key, 'val> Expression<'val> for Excluded<'key> {
+    fn build(self, ctx: &mut Context<'val>) -> Result<(), Error> {
+        match ctx.dialect() {
+            System::Mysql => {
+                ctx.write_str("VALUES(")?;
+                ctx.push_identifier(&self.column)?;
+                ctx.write_char(')')?;
+            }
+            System::Sqlite | System::LibSql | System::Postgres => {
+                ctx.write_str("excluded.")?;
+                ctx.push_identifier(&self.column)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct OnConflict<'key, S> {
+    stmt: S,
+    columns: Vec<Cow<'key, str>>,
+}
+
+impl<'key, S> OnConflict<'key, S> {
+    pub fn new<I, C>(stmt: S, columns: I) -> OnConflict<'key, S>
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Cow<'key, str>>,
+    {
+        OnConflict {
+            stmt,
+            columns: columns.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn do_nothing<'val>(self) -> Upsert<'key, 'val, S> {
+        Upsert {
+            conflict: self,
+            action: ConflictAction::Nothing,
+            keys: Vec::default(),
+            values: Vec::default(),
+        }
+    }
+
+    /// Update the existing row instead, with the columns given through `Set`
+    pub fn do_update<'val>(self) -> Upsert<'key, 'val, S> {
+        Upsert {
+            conflict: self,
+            action: ConflictAction::Update,
+            keys: Vec::default(),
+            values: Vec::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConflictAction {
+    Nothing,
+    Update,
+}
+
+/// An insert statement which resolves conflicts on the given columns.
+///
+/// MySQL has no conflict target and resolves conflicts on any unique key,
+/// there `do_nothing` becomes a no-op update of the first conflict column.
+/// Postgres and SQLite need the conflict columns to `do_update`.
+#[derive(Clone)]
+pub struct Upsert<'key, 'val, S> {
+    conflict: OnConflict<'key, S>,
+    action: ConflictAction,
+    keys: Vec<Cow<'key, str>>,
+    values: Vec<ExpressionBox<'val>>,
+}
+
+impl<'key, 'val, S> Set<'key, 'val> for Upsert<'key, 'val, S>
+where
+    S: Statement<'val>,
+{
+    fn set<F, V>(&mut self, field: F, value: V) -> &mut Self
+    where
+        F: Into<Cow<'key, str>>,
+        V: Expression<'val> + Send + Sync + Clone + 'val,
+    {
+        self.keys.push(field.into());
+        self.values.push(expr_box(value));
+        self
+    }
+
+    fn build(self, ctx: &mut Context<'val>) -> Result<(), Error> {
+        <Self as Statement>::build(self, ctx)
+    }
+}
+
+impl<'key, 'val, S> Returning<'val> for Upsert<'key, 'val, S> {}
+
+impl<'key, 'val, S> Upsert<'key, 'val, S> {
+    fn build_set(
+        keys: Vec<Cow<'key, str>>,
+        values: Vec<ExpressionBox<'val>>,
+        ctx: &mut Context<'val>,
+    ) -> Result<(), Error> {
+        for (idx, (key, value)) in keys.iter().zip(values).enumerate() {
+            if idx > 0 {
+                ctx.write_str(",")?;
+            }
+            ctx.push_identifier(key)?;
+            ctx.write_str(" = ")?;
+            value.build(ctx)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'key, 'val, S> Statement<'val> for Upsert<'key, 'val, S>
+where
+    S: Statement<'val>,
+{
+    fn build(self, ctx: &mut Context<'val>) -> Result<(), Error> {
+        let Upsert {
+            conflict: OnConflict { stmt, columns },
+            action,
+            keys,
+            values,
+        } = self;
+
+        // No dialect has an empty SET
+        if action == ConflictAction::Update && keys.is_empty() {
+            return Err(Error::Unsupported {
+                dialect: ctx.dialect(),
+                feature: "ON CONFLICT DO UPDATE without columns to update",
+            });
+        }
+
+        // Only DO NOTHING can leave out the conflict target
+        if action == ConflictAction::Update && columns.is_empty() && ctx.dialect() != System::Mysql
+        {
+            return Err(Error::Unsupported {
+                dialect: ctx.dialect(),
+                feature: "ON CONFLICT DO UPDATE without a conflict column",
+            });
+        }
+
+        stmt.build(ctx)?;
+
+        if ctx.dialect() == System::Mysql {
+            ctx.write_str(" ON DUPLICATE KEY UPDATE ")?;
+            return match action {
+                ConflictAction::Nothing => {
+                    let Some(column) = columns.first() else {
+                        return Err(Error::Unsupported {
+                            dialect: System::Mysql,
+                            feature: "ON CONFLICT DO NOTHING without a conflict column",
+                        });
+                    };
+                    ctx.push_identifier(column)?;
+                    ctx.write_str(" = ")?;
+                    ctx.push_identifier(column)?;
+                    Ok(())
+                }
+                ConflictAction::Update => Self::build_set(keys, values, ctx),
+            };
+        }
+
+        ctx.write_str(" ON CONFLICT")?;
+        if !columns.is_empty() {
+            ctx.write_str(" (")?;
+            for (idx, column) in columns.iter().enumerate() {
+                if idx > 0 {
+                    ctx.write_str(",")?;
+                }
+                ctx.push_identifier(column)?;
+            }
+            ctx.write_char(')')?;
+        }
+
+        match action {
+            ConflictAction::Nothing => ctx.write_str(" DO NOTHING")?,
+            ConflictAction::Update => {
+                ctx.write_str(" DO UPDATE SET ")?;
+                Self::build_set(keys, values, ctx)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{StatementExt, expr::val, mutate::Insert};
+
+    fn upsert<'a>() -> Upsert<'a, 'a, Insert<'a, 'a>> {
+        Insert::new("users")
+            .with("id", val(1))
+            .with("name", val("rust"))
+            .on_conflict(["id"])
+            .do_update()
+            .with("name", excluded("name"))
+    }
+
+    #[test]
+    fn test_upsert_build() {
+        let stmt = upsert().to_sql(System::Postgres).expect("build");
+        assert_eq!(
+            stmt.sql,
+            r#"INSERT INTO "users" ("id","name") VALUES ($1,$2) ON CONFLICT ("id") DO UPDATE SET "name" = excluded."name""#
+        );
+
+        let stmt = upsert().to_sql(System::Mysql).expect("build");
+        assert_eq!(
+            stmt.sql,
+            "INSERT INTO `users` (`id`,`name`) VALUES (?,?) ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)"
+        );
+    }
+
+    #[test]
+    fn test_do_update_without_columns() {
+        let upsert = || {
+            Insert::new("users")
+                .with("id", val(1))
+                .with("name", val("rust"))
+                .on_conflict(Vec::<&str>::new())
+                .do_update()
+                .with("name", excluded("name"))
+        };
+
+        for dialect in [System::Postgres, System::Sqlite] {
+            assert!(matches!(
+                upsert().to_sql(dialect),
+                Err(Error::Unsupported { .. })
+            ));
+        }
+
+        let stmt = upsert().to_sql(System::Mysql).expect("build");
+        assert_eq!(
+            stmt.sql,
+            "INSERT INTO `users` (`id`,`name`) VALUES (?,?) ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)"
+        );
+    }
+
+    #[test]
+    fn test_do_update_without_set() {
+        for dialect in [System::Postgres, System::Sqlite, System::Mysql] {
+            let stmt = Insert::new("users")
+                .with("id", val(1))
+                .on_conflict(["id"])
+                .do_update()
+                .to_sql(dialect);
+
+            assert!(matches!(
+                stmt,
+                Err(Error::Unsupported {
+                    feature: "ON CONFLICT DO UPDATE without columns to update",
+                    ..
+                })
+            ));
+        }
+    }
+}
@@ -1,5 +1,16 @@
-use crate::{Context, Error, expr::Ident, schema::Column, statement::Statement};
+use crate::{
+    Context, Error,
+    expr::{Expression, Ident},
+    schema::{
+        Column, ColumnType, Constraint, CreateTable, DropTable, ForeignKey,
+        constraint::write_columns, ty::write_sql_type,
+    },
+    sql::SqlStmt,
+    statement::{Statement, StatementExt},
+};
+use alloc::{borrow::Cow, format, vec, vec::Vec};
 use core::fmt::Write;
+use usql_core::System;
 
 pub fn alter_table<'a, T, O>(table: T, operation: O) -> AlterTable<T, O> {
     AlterTable { table, operation }
@@ -88,3 +99,487 @@ where
         self.1.build(ctx)
     }
 }
+
+/// Changes the type of a column.
+///
+/// MySQL redefines the whole column, so its NOT NULL and default are reset.
+pub struct AlterColumnType<'a, T>(pub T, pub ColumnType<'a>);
+
+impl<'a, T> AlterOperation<'a> for AlterColumnType<'a, T>
+where
+    T: Ident<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        let dialect = ctx.dialect();
+        match dialect {
+            System::Postgres => {
+                ctx.write_str("ALTER COLUMN ")?;
+                self.0.build(ctx)?;
+                ctx.write_str(" TYPE ")?;
+                write_sql_type(&self.1, ctx, dialect)?;
+            }
+            System::Mysql => {
+                ctx.write_str("MODIFY COLUMN ")?;
+                self.0.build(ctx)?;
+                ctx.write_char(' ')?;
+                write_sql_type(&self.1, ctx, dialect)?;
+            }
+            System::Sqlite | System::LibSql => return Err(rebuild_required(dialect)),
+        }
+        Ok(())
+    }
+}
+
+pub struct SetDefault<T, E>(pub T, pub E);
+
+impl<'a, T, E> AlterOperation<'a> for SetDefault<T, E>
+where
+    T: Ident<'a>,
+    E: Expression<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        native_alter(ctx)?;
+        ctx.write_str("ALTER COLUMN ")?;
+        self.0.build(ctx)?;
+        ctx.write_str(" SET DEFAULT (")?;
+        self.1.build(ctx)?;
+        ctx.write_char(')')?;
+        Ok(())
+    }
+}
+
+pub struct DropDefault<T>(pub T);
+
+impl<'a, T> AlterOperation<'a> for DropDefault<T>
+where
+    T: Ident<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        native_alter(ctx)?;
+        ctx.write_str("ALTER COLUMN ")?;
+        self.0.build(ctx)?;
+        ctx.write_str(" DROP DEFAULT")?;
+        Ok(())
+    }
+}
+
+/// Makes a column required, MySQL needs its type to redefine it.
+pub struct SetNotNull<'a, T>(pub T, pub ColumnType<'a>);
+
+impl<'a, T> AlterOperation<'a> for SetNotNull<'a, T>
+where
+    T: Ident<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        build_nullability(ctx, self.0, self.1, true)
+    }
+}
+
+/// Makes a column optional, MySQL needs its type to redefine it.
+pub struct DropNotNull<'a, T>(pub T, pub ColumnType<'a>);
+
+impl<'a, T> AlterOperation<'a> for DropNotNull<'a, T>
+where
+    T: Ident<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        build_nullability(ctx, self.0, self.1, false)
+    }
+}
+
+fn build_nullability<'a, T>(
+    ctx: &mut Context<'a>,
+    column: T,
+    ty: ColumnType<'a>,
+    required: bool,
+) -> Result<(), Error>
+where
+    T: Ident<'a>,
+{
+    native_alter(ctx)?;
+    let dialect = ctx.dialect();
+    if dialect == System::Mysql {
+        ctx.write_str("MODIFY COLUMN ")?;
+        column.build(ctx)?;
+        ctx.write_char(' ')?;
+        write_sql_type(&ty, ctx, dialect)?;
+        ctx.write_str(if required { " NOT NULL" } else { " NULL" })?;
+    } else {
+        ctx.write_str("ALTER COLUMN ")?;
+        column.build(ctx)?;
+        ctx.write_str(if required {
+            " SET NOT NULL"
+        } else {
+            " DROP NOT NULL"
+        })?;
+    }
+    Ok(())
+}
+
+pub struct AddConstraint<'a>(pub Constraint<'a>);
+
+impl<'a> AlterOperation<'a> for AddConstraint<'a> {
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        native_alter(ctx)?;
+        ctx.write_str("ADD ")?;
+        self.0.build(ctx)
+    }
+}
+
+pub struct DropConstraint<T>(pub T);
+
+impl<'a, T> AlterOperation<'a> for DropConstraint<T>
+where
+    T: Ident<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        native_alter(ctx)?;
+        ctx.write_str("DROP CONSTRAINT ")?;
+        self.0.build(ctx)
+    }
+}
+
+pub struct AddForeignKey<'a> {
+    pub name: Cow<'a, str>,
+    pub column: Cow<'a, str>,
+    pub foreign_key: ForeignKey<'a>,
+}
+
+impl<'a> AddForeignKey<'a> {
+    pub fn new(
+        name: impl Into<Cow<'a, str>>,
+        column: impl Into<Cow<'a, str>>,
+        foreign_key: ForeignKey<'a>,
+    ) -> AddForeignKey<'a> {
+        AddForeignKey {
+            name: name.into(),
+            column: column.into(),
+            foreign_key,
+        }
+    }
+}
+
+impl<'a> AlterOperation<'a> for AddForeignKey<'a> {
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        native_alter(ctx)?;
+        ctx.write_str("ADD CONSTRAINT ")?;
+        ctx.push_identifier(&self.name)?;
+        ctx.write_str(" FOREIGN KEY (")?;
+        ctx.push_identifier(&self.column)?;
+        ctx.write_str(") ")?;
+        self.foreign_key.build_references(ctx)
+    }
+}
+
+pub struct DropForeignKey<T>(pub T);
+
+impl<'a, T> AlterOperation<'a> for DropForeignKey<T>
+where
+    T: Ident<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        native_alter(ctx)?;
+        if ctx.dialect() == System::Mysql {
+            ctx.write_str("DROP FOREIGN KEY ")?;
+        } else {
+            ctx.write_str("DROP CONSTRAINT ")?;
+        }
+        self.0.build(ctx)
+    }
+}
+
+fn rebuild_required(dialect: System) -> Error {
+    Error::Unsupported {
+        dialect,
+        feature: "ALTER TABLE without a table rebuild",
+    }
+}
+
+// SQLite can only rename, add and drop columns in place
+fn native_alter(ctx: &Context<'_>) -> Result<(), Error> {
+    match ctx.dialect() {
+        dialect @ (System::Sqlite | System::LibSql) => Err(rebuild_required(dialect)),
+        System::Postgres | System::Mysql => Ok(()),
+    }
+}
+
+/// Emulates an `ALTER TABLE` on SQLite, which can't change columns or
+/// constraints in place, by rebuilding the table from its new definition.
+///
+/// [`RebuildTable::steps`] are, on SQLite, the steps of its documented
+/// procedure: the new table is created, the rows copied, the old table dropped
+/// and the new one renamed. They have to run in a transaction with foreign keys
+/// turned off, checked with `PRAGMA foreign_key_check` before the commit, which
+/// a single script can't do, so it isn't a [`Statement`] on SQLite. Indexes and
+/// triggers on the old table are dropped with it and have to be created again.
+/// Other dialects run the wrapped statement.
+pub struct RebuildTable<'a, S> {
+    stmt: S,
+    table: CreateTable<'a>,
+    columns: Option<Vec<(Cow<'a, str>, Cow<'a, str>)>>,
+}
+
+impl<T, O> AlterTable<T, O> {
+    pub fn sqlite_rebuild<'a>(self, table: CreateTable<'a>) -> RebuildTable<'a, Self> {
+        RebuildTable {
+            stmt: self,
+            table,
+            columns: None,
+        }
+    }
+}
+
+impl<'a, S> RebuildTable<'a, S> {
+    /// Columns copied to the new table as `(old, new)` pairs,
+    /// defaults to all of the new table's columns under the same name
+    pub fn copy_columns(mut self, columns: Vec<(Cow<'a, str>, Cow<'a, str>)>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+}
+
+impl<'a, S> RebuildTable<'a, S>
+where
+    S: Statement<'a>,
+{
+    /// The statements to run, in order
+    pub fn steps(self, dialect: System) -> Result<Vec<SqlStmt<'a>>, Error> {
+        if !matches!(dialect, System::Sqlite | System::LibSql) {
+            return Ok(vec![self.stmt.to_sql(dialect)?]);
+        }
+
+        let RebuildTable {
+            mut table, columns, ..
+        } = self;
+
+        let name = table.name.clone();
+        let temp = format!("_usql_new_{name}");
+        let (old, new): (Vec<_>, Vec<_>) = columns
+            .unwrap_or_else(|| {
+                table
+                    .fields
+                    .iter()
+                    .map(|m| (m.name.clone(), m.name.clone()))
+                    .collect()
+            })
+            .into_iter()
+            .unzip();
+
+        table.name = temp.clone().into();
+        table.force = true;
+
+        let create = table.to_sql(dialect)?;
+
+        let mut ctx = Context::new(dialect);
+        ctx.write_str("INSERT INTO ")?;
+        ctx.push_identifier(&temp)?;
+        ctx.write_str(" (")?;
+        write_columns(&mut ctx, &new)?;
+        ctx.write_str(") SELECT ")?;
+        write_columns(&mut ctx, &old)?;
+        ctx.write_str(" FROM ")?;
+        ctx.push_identifier(&name)?;
+        let copy = ctx.build();
+
+        let drop = DropTable::new(name.clone()).to_sql(dialect)?;
+
+        let mut ctx = Context::new(dialect);
+        ctx.write_str("ALTER TABLE ")?;
+        ctx.push_identifier(&temp)?;
+        ctx.write_str(" RENAME TO ")?;
+        ctx.push_identifier(&name)?;
+        let rename = ctx.build();
+
+        Ok(vec![create, copy, drop, rename])
+    }
+}
+
+impl<'a, S> Statement<'a> for RebuildTable<'a, S>
+where
+    S: Statement<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        match ctx.dialect() {
+            dialect @ (System::Sqlite | System::LibSql) => Err(Error::Unsupported {
+                dialect,
+                feature: "a table rebuild as a single statement",
+            }),
+            System::Postgres | System::Mysql => self.stmt.build(ctx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        StatementExt,
+        expr::Call,
+        schema::{ReferentialAction, create_table},
+    };
+
+    fn alter()
+    -> RebuildTable<'static, AlterTable<&'static str, AlterColumnType<'static, &'static str>>> {
+        alter_table("users", AlterColumnType("age", ColumnType::BigInt)).sqlite_rebuild(
+            create_table("users")
+                .column(Column::new("id", ColumnType::Int).primary_key())
+                .column(Column::new("age", ColumnType::BigInt).not_null()),
+        )
+    }
+
+    #[test]
+    fn test_alter_column_type() {
+        let stmt = alter().to_sql(System::Postgres).expect("build");
+        assert_eq!(
+            stmt.sql,
+            r#"ALTER TABLE "users" ALTER COLUMN "age" TYPE BIGINT"#
+        );
+
+        let steps = alter().steps(System::Postgres).expect("build");
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].sql, stmt.sql);
+
+        let steps = alter().steps(System::Sqlite).expect("build");
+        let steps: Vec<_> = steps.iter().map(|m| m.sql.as_str()).collect();
+        assert_eq!(
+            steps,
+            [
+                r#"CREATE TABLE "_usql_new_users" ("id" INTEGER PRIMARY KEY DEFAULT NULL, "age" INTEGER NOT NULL)"#,
+                r#"INSERT INTO "_usql_new_users" ("id","age") SELECT "id","age" FROM "users""#,
+                r#"DROP TABLE "users""#,
+                r#"ALTER TABLE "_usql_new_users" RENAME TO "users""#,
+            ]
+        );
+
+        assert!(matches!(
+            alter().to_sql(System::Sqlite),
+            Err(Error::Unsupported { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rebuild_copy_columns() {
+        let steps = alter_table("users", RenameColumn("name", "full_name"))
+            .sqlite_rebuild(
+                create_table("users").column(Column::new("full_name", ColumnType::Text).not_null()),
+            )
+            .copy_columns(alloc::vec![("name".into(), "full_name".into())])
+            .steps(System::Sqlite)
+            .expect("build");
+
+        assert_eq!(
+            steps[1].sql,
+            r#"INSERT INTO "_usql_new_users" ("full_name") SELECT "name" FROM "users""#
+        );
+    }
+
+    #[test]
+    fn test_alter_default() {
+        let stmt = alter_table("users", SetDefault("created", Call::new("now", ())))
+            .to_sql(System::Postgres)
+            .expect("build");
+        assert_eq!(
+            stmt.sql,
+            r#"ALTER TABLE "users" ALTER COLUMN "created" SET DEFAULT (now())"#
+        );
+
+        let stmt = alter_table("users", DropDefault("created"))
+            .to_sql(System::Mysql)
+            .expect("build");
+        assert_eq!(
+            stmt.sql,
+            "ALTER TABLE `users` ALTER COLUMN `created` DROP DEFAULT"
+        );
+    }
+
+    #[test]
+    fn test_alter_nullability() {
+        let stmt = alter_table("users", SetNotNull("email", ColumnType::VarChar(255)))
+            .to_sql(System::Mysql)
+            .expect("build");
+        assert_eq!(
+            stmt.sql,
+            "ALTER TABLE `users` MODIFY COLUMN `email` VARCHAR(255) NOT NULL"
+        );
+
+        let stmt = alter_table("users", DropNotNull("email", ColumnType::VarChar(255)))
+            .to_sql(System::Mysql)
+            .expect("build");
+        assert_eq!(
+            stmt.sql,
+            "ALTER TABLE `users` MODIFY COLUMN `email` VARCHAR(255) NULL"
+        );
+
+        let stmt = alter_table("users", DropNotNull("email", ColumnType::VarChar(255)))
+            .to_sql(System::Postgres)
+            .expect("build");
+        assert_eq!(
+            stmt.sql,
+            r#"ALTER TABLE "users" ALTER COLUMN "email" DROP NOT NULL"#
+        );
+    }
+
+    #[test]
+    fn test_alter_foreign_key() {
+        let add = || {
+            alter_table(
+                "users",
+                AddForeignKey::new(
+                    "users_team_fk",
+                    "team_id",
+                    ForeignKey::new("teams", "id").on_delete(ReferentialAction::Cascade),
+                ),
+            )
+        };
+
+        let stmt = add().to_sql(System::Postgres).expect("build");
+        assert_eq!(
+            stmt.sql,
+            r#"ALTER TABLE "users" ADD CONSTRAINT "users_team_fk" FOREIGN KEY ("team_id") REFERENCES "teams"("id") ON DELETE CASCADE ON UPDATE NO ACTION"#
+        );
+
+        let stmt = add().to_sql(System::Mysql).expect("build");
+        assert_eq!(
+            stmt.sql,
+            "ALTER TABLE `users` ADD CONSTRAINT `users_team_fk` FOREIGN KEY (`team_id`) REFERENCES `teams`(`id`) ON DELETE CASCADE ON UPDATE NO ACTION"
+        );
+
+        let stmt = alter_table("users", DropForeignKey("users_team_fk"))
+            .to_sql(System::Postgres)
+            .expect("build");
+        assert_eq!(
+            stmt.sql,
+            r#"ALTER TABLE "users" DROP CONSTRAINT "users_team_fk""#
+        );
+
+        let stmt = alter_table("users", DropForeignKey("users_team_fk"))
+            .to_sql(System::Mysql)
+            .expect("build");
+        assert_eq!(
+            stmt.sql,
+            "ALTER TABLE `users` DROP FOREIGN KEY `users_team_fk`"
+        );
+    }
+
+    #[test]
+    fn test_alter_sqlite_unsupported() {
+        let unsupported = |result: Result<_, Error>| {
+            assert!(matches!(
+                result,
+                Err(Error::Unsupported {
+                    dialect: System::Sqlite,
+                    ..
+                })
+            ))
+        };
+
+        unsupported(
+            alter_table("users", AlterColumnType("age", ColumnType::BigInt)).to_sql(System::Sqlite),
+        );
+        unsupported(alter_table("users", DropDefault("created")).to_sql(System::Sqlite));
+        unsupported(
+            alter_table("users", SetNotNull("email", ColumnType::Text)).to_sql(System::Sqlite),
+        );
+        unsupported(alter_table("users", DropForeignKey("users_team_fk")).to_sql(System::Sqlite));
+    }
+}
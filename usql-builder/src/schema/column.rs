@@ -19,6 +19,8 @@ pub struct Column<'a> {
     pub auto: bool,
     pub default: Option<ExpressionBox<'a>>,
     pub foreign_key: Option<ForeignKey<'a>>,
+    pub unique: bool,
+    pub check: Option<ExpressionBox<'a>>,
 }
 
 impl<'a> Column<'a> {
@@ -31,6 +33,8 @@ impl<'a> Column<'a> {
             default: None,
             foreign_key: None,
             auto: false,
+            unique: false,
+            check: None,
         }
     }
 
@@ -54,6 +58,19 @@ impl<'a> Column<'a> {
         self
     }
 
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    pub fn check<E>(mut self, expr: E) -> Self
+    where
+        E: Expression<'a> + Send + Sync + Clone + 'a,
+    {
+        self.check = Some(expr_box(expr));
+        self
+    }
+
     pub fn required(mut self, required: bool) -> Self {
         self.required = required;
         self
@@ -77,6 +94,16 @@ impl<'a> Column<'a> {
 
         let dialect = ctx.dialect();
 
+        if (self.primary_key || self.unique)
+            && dialect == System::Mysql
+            && !self.kind.mysql_keyable()
+        {
+            return Err(Error::Unsupported {
+                dialect,
+                feature: "keys on TEXT, BLOB and JSON columns",
+            });
+        }
+
         if !(self.auto && dialect == System::Postgres) {
             write_sql_type(&self.kind, ctx, dialect)?;
         } else {
@@ -93,7 +120,7 @@ impl<'a> Column<'a> {
             write!(ctx, " PRIMARY KEY")?;
         }
 
-        if !(self.auto && dialect == System::Sqlite) {
+        if !(self.auto && matches!(dialect, System::Sqlite | System::Mysql)) {
             if self.required && self.default.is_none() {
                 ctx.write_str(" NOT NULL")?;
             } else if let Some(default) = &self.default {
@@ -108,25 +135,24 @@ impl<'a> Column<'a> {
         // TODO: Validate type
         if self.auto && dialect == System::Sqlite {
             ctx.write_str(" AUTOINCREMENT")?;
+        } else if self.auto && dialect == System::Mysql {
+            ctx.write_str(" AUTO_INCREMENT")?;
         }
 
-        if let Some(foreign_k) = &self.foreign_key {
-            if fk {
-                ctx.write_str(" REFERENCES ")?;
-
-                ctx.push_identifier(&foreign_k.table)?;
-
-                ctx.write_char('(')?;
-
-                ctx.push_identifier(&foreign_k.column)?;
+        if self.unique {
+            ctx.write_str(" UNIQUE")?;
+        }
 
-                ctx.write_char(')')?;
+        if let Some(check) = &self.check {
+            ctx.write_str(" CHECK (")?;
+            check.clone().build(ctx)?;
+            ctx.write_char(')')?;
+        }
 
-                write!(
-                    ctx,
-                    " ON DELETE {} ON UPDATE {}",
-                    foreign_k.on_delete, foreign_k.on_update
-                )?;
+        if let Some(foreign_k) = &self.foreign_key {
+            if fk {
+                ctx.write_char(' ')?;
+                foreign_k.build_references(ctx)?;
             }
         }
 
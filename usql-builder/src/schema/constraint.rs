@@ -1,8 +1,12 @@
-use core::fmt::Write;
+use core::fmt::{self, Write};
 
 use alloc::{borrow::Cow, vec::Vec};
 
-use crate::{Context, Error};
+use crate::{
+    Context, Error,
+    expr::{Expression, ExpressionBox, expr_box},
+    schema::ReferentialAction,
+};
 
 #[derive(Debug, Clone)]
 pub struct Constraint<'a> {
@@ -18,28 +22,229 @@ impl<'a> Constraint<'a> {
         }
     }
 
+    pub fn unique(name: impl Into<Cow<'a, str>>, cols: Vec<Cow<'a, str>>) -> Constraint<'a> {
+        Constraint {
+            name: name.into(),
+            kind: ConstraintKind::Unique(cols),
+        }
+    }
+
+    pub fn check<E>(name: impl Into<Cow<'a, str>>, expr: E) -> Constraint<'a>
+    where
+        E: Expression<'a> + Send + Sync + Clone + 'a,
+    {
+        Constraint {
+            name: name.into(),
+            kind: ConstraintKind::Check(expr_box(expr)),
+        }
+    }
+
+    pub fn foreign_key(
+        name: impl Into<Cow<'a, str>>,
+        foreign_key: CompositeForeignKey<'a>,
+    ) -> Constraint<'a> {
+        Constraint {
+            name: name.into(),
+            kind: ConstraintKind::ForeignKey(foreign_key),
+        }
+    }
+
     pub(crate) fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
         ctx.write_str("CONSTRAINT ")?;
         ctx.push_identifier(&self.name)?;
         match self.kind {
             ConstraintKind::PrimaryKey(fields) => {
                 write!(ctx, " PRIMARY KEY (")?;
-                for (idx, pkg) in fields.into_iter().enumerate() {
-                    if idx > 0 {
-                        write!(ctx, ",")?;
-                    }
-
-                    ctx.push_identifier(&pkg)?;
-                }
+                write_columns(ctx, &fields)?;
+                ctx.write_char(')')?;
+            }
+            ConstraintKind::Unique(fields) => {
+                write!(ctx, " UNIQUE (")?;
+                write_columns(ctx, &fields)?;
                 ctx.write_char(')')?;
             }
+            ConstraintKind::Check(expr) => {
+                write!(ctx, " CHECK (")?;
+                expr.build(ctx)?;
+                ctx.write_char(')')?;
+            }
+            ConstraintKind::ForeignKey(fk) => {
+                write!(ctx, " FOREIGN KEY (")?;
+                write_columns(ctx, &fk.columns)?;
+                ctx.write_str(") REFERENCES ")?;
+                ctx.push_identifier(&fk.table)?;
+                ctx.write_char('(')?;
+                write_columns(ctx, &fk.references)?;
+                write!(
+                    ctx,
+                    ") ON DELETE {} ON UPDATE {}",
+                    fk.on_delete, fk.on_update
+                )?;
+            }
         };
 
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
+pub(crate) fn write_columns(ctx: &mut Context<'_>, columns: &[Cow<'_, str>]) -> Result<(), Error> {
+    for (idx, column) in columns.iter().enumerate() {
+        if idx > 0 {
+            write!(ctx, ",")?;
+        }
+
+        ctx.push_identifier(column)?;
+    }
+    Ok(())
+}
+
+#[derive(Clone)]
 pub enum ConstraintKind<'a> {
     PrimaryKey(Vec<Cow<'a, str>>),
+    Unique(Vec<Cow<'a, str>>),
+    Check(ExpressionBox<'a>),
+    ForeignKey(CompositeForeignKey<'a>),
+}
+
+impl<'a> fmt::Debug for ConstraintKind<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PrimaryKey(cols) => f.debug_tuple("PrimaryKey").field(cols).finish(),
+            Self::Unique(cols) => f.debug_tuple("Unique").field(cols).finish(),
+            Self::Check(_) => f.debug_tuple("Check").finish_non_exhaustive(),
+            Self::ForeignKey(fk) => f.debug_tuple("ForeignKey").field(fk).finish(),
+        }
+    }
+}
+
+/// A foreign key over one or more columns, `columns -> table(references)`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CompositeForeignKey<'a> {
+    pub columns: Vec<Cow<'a, str>>,
+    pub table: Cow<'a, str>,
+    pub references: Vec<Cow<'a, str>>,
+    pub on_update: ReferentialAction,
+    pub on_delete: ReferentialAction,
+}
+
+impl<'a> CompositeForeignKey<'a> {
+    pub fn new(
+        columns: Vec<Cow<'a, str>>,
+        table: impl Into<Cow<'a, str>>,
+        references: Vec<Cow<'a, str>>,
+    ) -> CompositeForeignKey<'a> {
+        CompositeForeignKey {
+            columns,
+            table: table.into(),
+            references,
+            on_delete: ReferentialAction::NoAction,
+            on_update: ReferentialAction::NoAction,
+        }
+    }
+
+    pub fn on_delete(mut self, action: ReferentialAction) -> Self {
+        self.on_delete = action;
+        self
+    }
+
+    pub fn on_update(mut self, action: ReferentialAction) -> Self {
+        self.on_update = action;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use usql_core::System;
+
+    use super::*;
+    use crate::{
+        StatementExt,
+        expr::ExpressionExt,
+        schema::{Column, ColumnType, CreateTable, create_table},
+    };
+
+    fn members<'a>(email: ColumnType<'a>) -> CreateTable<'a> {
+        create_table("members")
+            .force()
+            .column(Column::new("team", ColumnType::Int).not_null())
+            .column(Column::new("user", ColumnType::Int).not_null())
+            .column(Column::new("email", email).unique())
+            .constraint(Constraint::unique(
+                "members_team_user",
+                alloc::vec!["team".into(), "user".into()],
+            ))
+            .constraint(Constraint::check("members_user", "user".neq("team")))
+            .constraint(Constraint::foreign_key(
+                "members_team_fk",
+                CompositeForeignKey::new(
+                    alloc::vec!["team".into(), "user".into()],
+                    "teams",
+                    alloc::vec!["id".into(), "owner".into()],
+                )
+                .on_delete(ReferentialAction::Cascade),
+            ))
+    }
+
+    #[test]
+    fn test_constraints_build() {
+        let stmt = members(ColumnType::VarChar(255))
+            .to_sql(System::Mysql)
+            .expect("build");
+
+        assert_eq!(
+            stmt.sql,
+            "CREATE TABLE `members` (`team` INT NOT NULL, `user` INT NOT NULL, `email` VARCHAR(255) DEFAULT NULL UNIQUE, \
+             CONSTRAINT `members_team_user` UNIQUE (`team`,`user`), CONSTRAINT `members_user` CHECK (`user` != `team`), \
+             CONSTRAINT `members_team_fk` FOREIGN KEY (`team`,`user`) REFERENCES `teams`(`id`,`owner`) ON DELETE CASCADE ON UPDATE NO ACTION)"
+        );
+
+        let stmt = members(ColumnType::Text)
+            .to_sql(System::Postgres)
+            .expect("build");
+
+        assert_eq!(
+            stmt.sql,
+            concat!(
+                r#"CREATE TABLE "members" ("team" INTEGER NOT NULL, "user" INTEGER NOT NULL, "email" TEXT DEFAULT NULL UNIQUE, "#,
+                r#"CONSTRAINT "members_team_user" UNIQUE ("team","user"), CONSTRAINT "members_user" CHECK ("user" != "team"), "#,
+                r#"CONSTRAINT "members_team_fk" FOREIGN KEY ("team","user") REFERENCES "teams"("id","owner") ON DELETE CASCADE ON UPDATE NO ACTION)"#
+            )
+        );
+
+        let stmt = members(ColumnType::Text)
+            .to_sql(System::Sqlite)
+            .expect("build");
+
+        assert_eq!(
+            stmt.sql,
+            concat!(
+                r#"CREATE TABLE "members" ("team" INTEGER NOT NULL, "user" INTEGER NOT NULL, "email" TEXT DEFAULT NULL UNIQUE, "#,
+                r#"CONSTRAINT "members_team_user" UNIQUE ("team","user"), CONSTRAINT "members_user" CHECK ("user" != "team"), "#,
+                r#"CONSTRAINT "members_team_fk" FOREIGN KEY ("team","user") REFERENCES "teams"("id","owner") ON DELETE CASCADE ON UPDATE NO ACTION)"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_mysql_text_keys() {
+        let result = members(ColumnType::Text).to_sql(System::Mysql);
+        assert!(matches!(
+            result,
+            Err(Error::Unsupported {
+                dialect: System::Mysql,
+                ..
+            })
+        ));
+
+        // Through a table constraint
+        let result = create_table("members")
+            .column(Column::new("email", ColumnType::Blob))
+            .constraint(Constraint::primary_key(
+                "members_pk",
+                alloc::vec!["email".into()],
+            ))
+            .to_sql(System::Mysql);
+        assert!(matches!(result, Err(Error::Unsupported { .. })));
+    }
 }
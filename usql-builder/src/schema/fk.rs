@@ -1,7 +1,9 @@
-use core::fmt;
+use core::fmt::{self, Write};
 
 use alloc::borrow::Cow;
 
+use crate::{Context, Error};
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ForeignKey<'a> {
     pub table: Cow<'a, str>,
@@ -29,6 +31,21 @@ impl<'a> ForeignKey<'a> {
         self.on_update = action;
         self
     }
+
+    /// Write the `REFERENCES table(column) ON DELETE .. ON UPDATE ..` part
+    pub(crate) fn build_references(&self, ctx: &mut Context<'_>) -> Result<(), Error> {
+        ctx.write_str("REFERENCES ")?;
+        ctx.push_identifier(&self.table)?;
+        ctx.write_char('(')?;
+        ctx.push_identifier(&self.column)?;
+        ctx.write_char(')')?;
+        write!(
+            ctx,
+            " ON DELETE {} ON UPDATE {}",
+            self.on_delete, self.on_update
+        )?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
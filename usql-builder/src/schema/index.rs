@@ -1,15 +1,164 @@
 use alloc::{borrow::Cow, vec::Vec};
+use usql_core::System;
 
-use crate::{Context, Error, statement::Statement};
+use crate::{
+    Context, Error,
+    expr::{Expression, ExpressionBox, expr_box},
+    schema::constraint::write_columns,
+    select::Order,
+    statement::Statement,
+};
 
-use core::fmt::Write as _;
+use core::fmt::{self, Write as _};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone)]
+enum IndexTarget<'a> {
+    Column(Cow<'a, str>),
+    Expression(ExpressionBox<'a>),
+}
+
+/// A column or expression in an index, with its sort order and collation
+#[derive(Clone)]
+pub struct IndexColumn<'a> {
+    target: IndexTarget<'a>,
+    order: Option<Order>,
+    collation: Option<Cow<'a, str>>,
+}
+
+impl<'a> IndexColumn<'a> {
+    pub fn new(column: impl Into<Cow<'a, str>>) -> IndexColumn<'a> {
+        IndexColumn {
+            target: IndexTarget::Column(column.into()),
+            order: None,
+            collation: None,
+        }
+    }
+
+    /// Index the result of an expression, MySQL needs 8.0.13 or later
+    pub fn expr<E>(expr: E) -> IndexColumn<'a>
+    where
+        E: Expression<'a> + Send + Sync + Clone + 'a,
+    {
+        IndexColumn {
+            target: IndexTarget::Expression(expr_box(expr)),
+            order: None,
+            collation: None,
+        }
+    }
+
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Not supported by MySQL
+    pub fn collate(mut self, collation: impl Into<Cow<'a, str>>) -> Self {
+        self.collation = Some(collation.into());
+        self
+    }
+
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        match self.target {
+            IndexTarget::Column(column) => ctx.push_identifier(&column)?,
+            IndexTarget::Expression(expr) => {
+                ctx.write_char('(')?;
+                expr.build(ctx)?;
+                ctx.write_char(')')?;
+            }
+        }
+
+        if let Some(collation) = self.collation {
+            if ctx.dialect() == System::Mysql {
+                return Err(Error::Unsupported {
+                    dialect: System::Mysql,
+                    feature: "index collation",
+                });
+            }
+            ctx.write_str(" COLLATE ")?;
+            ctx.push_identifier(&collation)?;
+        }
+
+        if let Some(order) = self.order {
+            write!(ctx, " {order}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Debug for IndexColumn<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("IndexColumn");
+        match &self.target {
+            IndexTarget::Column(column) => debug.field("column", column),
+            IndexTarget::Expression(_) => debug.field("expr", &".."),
+        };
+        debug
+            .field("order", &self.order)
+            .field("collation", &self.collation)
+            .finish()
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for IndexColumn<'a> {
+    fn from(value: Cow<'a, str>) -> Self {
+        IndexColumn::new(value)
+    }
+}
+
+impl<'a> From<&'a str> for IndexColumn<'a> {
+    fn from(value: &'a str) -> Self {
+        IndexColumn::new(value)
+    }
+}
+
+/// The index method, checked against the dialect when the index is built
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IndexMethod {
+    BTree,
+    /// Postgres and MySQL, InnoDB quietly builds a B-tree instead
+    Hash,
+    /// Postgres only
+    Gin,
+    /// Postgres only
+    Gist,
+    /// Postgres only
+    Brin,
+}
+
+impl IndexMethod {
+    fn name(self, dialect: System) -> Result<&'static str, Error> {
+        let method = match (self, dialect) {
+            (IndexMethod::BTree, System::Postgres) => "btree",
+            (IndexMethod::Hash, System::Postgres) => "hash",
+            (IndexMethod::Gin, System::Postgres) => "gin",
+            (IndexMethod::Gist, System::Postgres) => "gist",
+            (IndexMethod::Brin, System::Postgres) => "brin",
+            (IndexMethod::BTree, System::Mysql) => "BTREE",
+            (IndexMethod::Hash, System::Mysql) => "HASH",
+            _ => {
+                return Err(Error::Unsupported {
+                    dialect,
+                    feature: "index method",
+                });
+            }
+        };
+
+        Ok(method)
+    }
+}
+
+#[derive(Clone)]
 pub struct CreateIndex<'a> {
     name: Cow<'a, str>,
     unique: bool,
     table: Cow<'a, str>,
-    columns: Vec<Cow<'a, str>>,
+    columns: Vec<IndexColumn<'a>>,
+    if_not_exists: bool,
+    concurrently: bool,
+    method: Option<IndexMethod>,
+    include: Vec<Cow<'a, str>>,
+    filter: Option<ExpressionBox<'a>>,
 }
 
 impl<'a> CreateIndex<'a> {
@@ -22,7 +171,12 @@ impl<'a> CreateIndex<'a> {
             name: name.into(),
             table: table.into(),
             unique: false,
-            columns,
+            columns: columns.into_iter().map(IndexColumn::new).collect(),
+            if_not_exists: true,
+            concurrently: false,
+            method: None,
+            include: Vec::default(),
+            filter: None,
         }
     }
 
@@ -30,16 +184,96 @@ impl<'a> CreateIndex<'a> {
         self.unique = unique;
         self
     }
+
+    pub fn column(mut self, column: impl Into<IndexColumn<'a>>) -> Self {
+        self.columns.push(column.into());
+        self
+    }
+
+    /// Enabled by default, MySQL has no `IF NOT EXISTS` for indexes and leaves it out
+    pub fn if_not_exists(mut self, if_not_exists: bool) -> Self {
+        self.if_not_exists = if_not_exists;
+        self
+    }
+
+    /// Build the index without locking out writes, Postgres only.
+    ///
+    /// It can't run inside a transaction. Other dialects return
+    /// `Error::Unsupported` when enabled.
+    pub fn concurrently(mut self, concurrently: bool) -> Self {
+        self.concurrently = concurrently;
+        self
+    }
+
+    /// The index method, all of them on Postgres and `BTree` or `Hash` on MySQL.
+    /// Not supported by SQLite
+    pub fn using(mut self, method: IndexMethod) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Non-key columns stored in the index, Postgres only
+    pub fn include(mut self, columns: Vec<Cow<'a, str>>) -> Self {
+        self.include = columns;
+        self
+    }
+
+    /// Only index the rows matching `filter`, not supported by MySQL
+    pub fn filter<E>(mut self, filter: E) -> Self
+    where
+        E: Expression<'a> + Send + Sync + Clone + 'a,
+    {
+        self.filter = Some(expr_box(filter));
+        self
+    }
+}
+
+impl<'a> fmt::Debug for CreateIndex<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateIndex")
+            .field("name", &self.name)
+            .field("unique", &self.unique)
+            .field("table", &self.table)
+            .field("columns", &self.columns)
+            .field("if_not_exists", &self.if_not_exists)
+            .field("concurrently", &self.concurrently)
+            .field("method", &self.method)
+            .field("include", &self.include)
+            .field("filter", &self.filter.as_ref().map(|_| ".."))
+            .finish()
+    }
 }
 
 impl<'a> Statement<'a> for CreateIndex<'a> {
     fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        let dialect = ctx.dialect();
+
+        let unsupported = |feature| Error::Unsupported { dialect, feature };
+        if !self.include.is_empty() && dialect != System::Postgres {
+            return Err(unsupported("INCLUDE columns"));
+        }
+        if self.filter.is_some() && dialect == System::Mysql {
+            return Err(unsupported("partial indexes"));
+        }
+        if self.concurrently && dialect != System::Postgres {
+            return Err(unsupported("CONCURRENTLY"));
+        }
+        let method = self.method.map(|method| method.name(dialect)).transpose()?;
+
         ctx.write_str("CREATE ")?;
         if self.unique {
             ctx.write_str("UNIQUE ")?;
         }
 
-        ctx.write_str("INDEX IF NOT EXISTS ")?;
+        ctx.write_str("INDEX ")?;
+
+        if self.concurrently {
+            ctx.write_str("CONCURRENTLY ")?;
+        }
+
+        if self.if_not_exists && dialect != System::Mysql {
+            ctx.write_str("IF NOT EXISTS ")?;
+        }
 
         ctx.push_identifier(&self.name)?;
 
@@ -47,40 +281,233 @@ impl<'a> Statement<'a> for CreateIndex<'a> {
 
         ctx.push_identifier(&self.table)?;
 
+        if let (Some(method), System::Postgres) = (method, dialect) {
+            write!(ctx, " USING {method} ")?;
+        }
+
         ctx.write_char('(')?;
 
-        for (k, v) in self.columns.iter().enumerate() {
+        for (k, v) in self.columns.into_iter().enumerate() {
             if k != 0 {
                 ctx.write_char(',')?;
             }
 
-            ctx.push_identifier(&v)?;
+            v.build(ctx)?;
         }
 
         ctx.write_char(')')?;
 
+        if let (Some(method), System::Mysql) = (method, dialect) {
+            write!(ctx, " USING {method}")?;
+        }
+
+        if !self.include.is_empty() {
+            ctx.write_str(" INCLUDE (")?;
+            write_columns(ctx, &self.include)?;
+            ctx.write_char(')')?;
+        }
+
+        if let Some(filter) = self.filter {
+            ctx.write_str(" WHERE ")?;
+            filter.build(ctx)?;
+        }
+
         Ok(())
     }
 }
 
+pub fn drop_index<'a>(index: impl Into<Cow<'a, str>>) -> DropIndex<'a> {
+    DropIndex::new(index)
+}
+
 pub struct DropIndex<'a> {
     index: Cow<'a, str>,
+    table: Option<Cow<'a, str>>,
+    if_exists: bool,
+    concurrently: bool,
 }
 
 impl<'a> DropIndex<'a> {
     pub fn new(index: impl Into<Cow<'a, str>>) -> DropIndex<'a> {
         DropIndex {
             index: index.into(),
+            table: None,
+            if_exists: false,
+            concurrently: false,
         }
     }
+
+    /// The indexed table, required by MySQL
+    pub fn on(mut self, table: impl Into<Cow<'a, str>>) -> Self {
+        self.table = Some(table.into());
+        self
+    }
+
+    /// Not supported by MySQL, which leaves it out
+    pub fn if_exists(mut self, if_exists: bool) -> Self {
+        self.if_exists = if_exists;
+        self
+    }
+
+    /// Postgres only, other dialects return `Error::Unsupported` when enabled
+    pub fn concurrently(mut self, concurrently: bool) -> Self {
+        self.concurrently = concurrently;
+        self
+    }
 }
 
 impl<'a> Statement<'a> for DropIndex<'a> {
     fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        let dialect = ctx.dialect();
+
+        if self.concurrently && dialect != System::Postgres {
+            return Err(Error::Unsupported {
+                dialect,
+                feature: "CONCURRENTLY",
+            });
+        }
+
         ctx.write_str("DROP INDEX ")?;
 
+        if self.concurrently {
+            ctx.write_str("CONCURRENTLY ")?;
+        }
+
+        if self.if_exists && dialect != System::Mysql {
+            ctx.write_str("IF EXISTS ")?;
+        }
+
         ctx.push_identifier(&self.index)?;
 
+        if dialect == System::Mysql {
+            let Some(table) = &self.table else {
+                return Err(Error::Unsupported {
+                    dialect,
+                    feature: "DROP INDEX without its table",
+                });
+            };
+            ctx.write_str(" ON ")?;
+            ctx.push_identifier(table)?;
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        StatementExt,
+        expr::{Call, ExpressionExt},
+    };
+
+    #[test]
+    fn test_create_index_build() {
+        let stmt = CreateIndex::new("documents", "documents_search", Vec::new())
+            .column(IndexColumn::expr(Call::new("lower", ("title",))))
+            .column(IndexColumn::new("created").order(Order::Desc))
+            .using(IndexMethod::BTree)
+            .include(alloc::vec!["id".into()])
+            .filter("deleted".eql("archived"))
+            .concurrently(true)
+            .to_sql(System::Postgres)
+            .expect("build");
+
+        assert_eq!(
+            stmt.sql,
+            r#"CREATE INDEX CONCURRENTLY IF NOT EXISTS "documents_search" ON "documents" USING btree ((lower("title")),"created" DESC) INCLUDE ("id") WHERE "deleted" = "archived""#
+        );
+
+        let result = drop_index("documents_search").to_sql(System::Mysql);
+        assert!(matches!(result, Err(Error::Unsupported { .. })));
+    }
+
+    #[test]
+    fn test_index_method() {
+        let index = || {
+            CreateIndex::new("users", "users_email", alloc::vec!["email".into()])
+                .if_not_exists(false)
+        };
+
+        let stmt = index()
+            .using(IndexMethod::Hash)
+            .to_sql(System::Mysql)
+            .expect("build");
+        assert_eq!(
+            stmt.sql,
+            "CREATE INDEX `users_email` ON `users` (`email`) USING HASH"
+        );
+
+        let stmt = index()
+            .using(IndexMethod::Gin)
+            .to_sql(System::Postgres)
+            .expect("build");
+        assert_eq!(
+            stmt.sql,
+            r#"CREATE INDEX "users_email" ON "users" USING gin ("email")"#
+        );
+
+        let result = index().using(IndexMethod::Gin).to_sql(System::Mysql);
+        assert!(matches!(
+            result,
+            Err(Error::Unsupported {
+                dialect: System::Mysql,
+                ..
+            })
+        ));
+
+        let result = index().using(IndexMethod::BTree).to_sql(System::Sqlite);
+        assert!(matches!(
+            result,
+            Err(Error::Unsupported {
+                dialect: System::Sqlite,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_concurrently_unsupported() {
+        for dialect in [System::Sqlite, System::LibSql, System::Mysql] {
+            let result = CreateIndex::new("users", "users_email", alloc::vec!["email".into()])
+                .concurrently(true)
+                .to_sql(dialect);
+            assert!(
+                matches!(
+                    result,
+                    Err(Error::Unsupported {
+                        feature: "CONCURRENTLY",
+                        ..
+                    })
+                ),
+                "{dialect:?}"
+            );
+
+            let result = drop_index("users_email")
+                .on("users")
+                .concurrently(true)
+                .to_sql(dialect);
+            assert!(
+                matches!(
+                    result,
+                    Err(Error::Unsupported {
+                        feature: "CONCURRENTLY",
+                        ..
+                    })
+                ),
+                "{dialect:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_index_debug() {
+        let index = CreateIndex::new("users", "users_email", alloc::vec!["email".into()])
+            .filter("deleted".eql("archived"));
+
+        let debug = alloc::format!("{:?}", index);
+        assert!(debug.starts_with("CreateIndex { name: \"users_email\""));
+        assert!(debug.ends_with("filter: Some(\"..\") }"));
+    }
+}
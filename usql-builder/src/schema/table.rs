@@ -2,9 +2,14 @@ use core::fmt::Write;
 
 use alloc::{borrow::Cow, vec::Vec};
 
+use usql_core::System;
+
 use crate::{
     Context, Error,
-    schema::{column::Column, constraint::Constraint},
+    schema::{
+        column::Column,
+        constraint::{Constraint, ConstraintKind},
+    },
     statement::Statement,
 };
 
@@ -52,8 +57,40 @@ impl<'a> CreateTable<'a> {
     }
 }
 
+impl<'a> CreateTable<'a> {
+    // Columns MySQL can't use in a UNIQUE or PRIMARY KEY constraint
+    fn check_mysql_keys(&self) -> Result<(), Error> {
+        for constraint in &self.constraints {
+            let (ConstraintKind::PrimaryKey(columns) | ConstraintKind::Unique(columns)) =
+                &constraint.kind
+            else {
+                continue;
+            };
+
+            let unkeyable = self
+                .fields
+                .iter()
+                .filter(|field| columns.contains(&field.name))
+                .any(|field| !field.kind.mysql_keyable());
+
+            if unkeyable {
+                return Err(Error::Unsupported {
+                    dialect: System::Mysql,
+                    feature: "keys on TEXT, BLOB and JSON columns",
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<'val> Statement<'val> for CreateTable<'val> {
     fn build(self, ctx: &mut Context<'val>) -> Result<(), Error> {
+        if ctx.dialect() == System::Mysql {
+            self.check_mysql_keys()?;
+        }
+
         ctx.write_str("CREATE")?;
         if self.temporary {
             ctx.write_str(" TEMPORARY")?;
@@ -84,28 +121,14 @@ impl<'val> Statement<'val> for CreateTable<'val> {
 
             ctx.push_identifier(name)?;
 
-            ctx.write_str(") REFERENCES ")?;
-
-            ctx.push_identifier(&fk.table)?;
+            ctx.write_str(") ")?;
 
-            ctx.write_char('(')?;
-
-            ctx.push_identifier(&fk.column)?;
-
-            ctx.write_char(')')?;
-
-            write!(
-                ctx,
-                " ON DELETE {} ON UPDATE {}",
-                fk.on_delete, fk.on_update
-            )?;
+            fk.build_references(ctx)?;
         }
 
-        for (idx, pkg) in self.constraints.into_iter().enumerate() {
-            if idx > 0 {
-                write!(ctx, ",")?;
-            }
-            pkg.build(ctx)?;
+        for constraint in self.constraints {
+            ctx.write_str(", ")?;
+            constraint.build(ctx)?;
         }
 
         ctx.write_str(")")?;
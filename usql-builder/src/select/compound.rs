@@ -0,0 +1,258 @@
+use core::fmt::Write;
+
+use usql_core::System;
+
+use crate::{
+    Either,
+    context::Context,
+    error::Error,
+    expr::Expression,
+    select::{
+        FilterSelect, GroupSelect, HavingSelect, JoinSelect, Joinable, LimitQuery, Select,
+        Selection, SortQuery, Target, query::Query,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SetOperator {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+
+impl core::fmt::Display for SetOperator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SetOperator::Union => f.write_str("UNION"),
+            SetOperator::UnionAll => f.write_str("UNION ALL"),
+            SetOperator::Intersect => f.write_str("INTERSECT"),
+            SetOperator::Except => f.write_str("EXCEPT"),
+        }
+    }
+}
+
+/// Two queries combined with a set operator.
+///
+/// Sorting or limiting it applies to the combined result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CompoundSelect<L, R> {
+    left: L,
+    right: R,
+    operator: SetOperator,
+}
+
+impl<L, R> CompoundSelect<L, R> {
+    pub fn new(left: L, right: R, operator: SetOperator) -> CompoundSelect<L, R> {
+        CompoundSelect {
+            left,
+            right,
+            operator,
+        }
+    }
+}
+
+/// Nested operands are parenthesized on Postgres and MySQL, where INTERSECT binds
+/// tighter than the other operators. SQLite doesn't accept parentheses, but evaluates
+/// left to right, so only a nested right operand is unsupported there.
+impl<'a, L, R> Query<'a> for CompoundSelect<L, R>
+where
+    L: CompoundQuery<'a>,
+    R: CompoundQuery<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        let dialect = ctx.dialect();
+        let parens = !matches!(dialect, System::Sqlite | System::LibSql);
+
+        if !parens && self.right.is_compound() {
+            return Err(Error::Unsupported {
+                dialect,
+                feature: "nested compound select as the right operand",
+            });
+        }
+
+        build_operand(self.left, ctx, parens)?;
+        write!(ctx, " {} ", self.operator)?;
+        build_operand(self.right, ctx, parens)?;
+        Ok(())
+    }
+}
+
+fn build_operand<'a, Q>(query: Q, ctx: &mut Context<'a>, parens: bool) -> Result<(), Error>
+where
+    Q: CompoundQuery<'a>,
+{
+    if parens && query.is_compound() {
+        ctx.write_char('(')?;
+        Query::build(query, ctx)?;
+        ctx.write_char(')')?;
+        Ok(())
+    } else {
+        Query::build(query, ctx)
+    }
+}
+
+/// Queries which can be an operand of a set operation.
+///
+/// Sorted and limited queries are left out, since SQLite doesn't accept
+/// ORDER BY or LIMIT before the last operand.
+pub trait CompoundQuery<'a>: Query<'a> + Sized {
+    fn union<Q: CompoundQuery<'a>>(self, query: Q) -> CompoundSelect<Self, Q> {
+        CompoundSelect::new(self, query, SetOperator::Union)
+    }
+
+    fn union_all<Q: CompoundQuery<'a>>(self, query: Q) -> CompoundSelect<Self, Q> {
+        CompoundSelect::new(self, query, SetOperator::UnionAll)
+    }
+
+    fn intersect<Q: CompoundQuery<'a>>(self, query: Q) -> CompoundSelect<Self, Q> {
+        CompoundSelect::new(self, query, SetOperator::Intersect)
+    }
+
+    fn except<Q: CompoundQuery<'a>>(self, query: Q) -> CompoundSelect<Self, Q> {
+        CompoundSelect::new(self, query, SetOperator::Except)
+    }
+
+    #[doc(hidden)]
+    fn is_compound(&self) -> bool {
+        false
+    }
+}
+
+impl<'a, L, R> CompoundQuery<'a> for Either<L, R>
+where
+    L: CompoundQuery<'a>,
+    R: CompoundQuery<'a>,
+{
+    fn is_compound(&self) -> bool {
+        match self {
+            Self::Left(left) => left.is_compound(),
+            Self::Right(right) => right.is_compound(),
+        }
+    }
+}
+
+impl<'a, L, R> CompoundQuery<'a> for CompoundSelect<L, R>
+where
+    L: CompoundQuery<'a>,
+    R: CompoundQuery<'a>,
+{
+    fn is_compound(&self) -> bool {
+        true
+    }
+}
+
+impl<'a, L, R> SortQuery<'a> for CompoundSelect<L, R>
+where
+    L: CompoundQuery<'a>,
+    R: CompoundQuery<'a>,
+{
+}
+
+impl<'a, L, R> LimitQuery<'a> for CompoundSelect<L, R>
+where
+    L: CompoundQuery<'a>,
+    R: CompoundQuery<'a>,
+{
+}
+
+impl<'a, T, S> CompoundQuery<'a> for Select<T, S>
+where
+    T: Target<'a>,
+    S: Selection<'a>,
+{
+}
+
+impl<'a, S, J> CompoundQuery<'a> for JoinSelect<S, J>
+where
+    S: Query<'a>,
+    J: Joinable<'a>,
+{
+}
+
+impl<'a, S, E> CompoundQuery<'a> for FilterSelect<S, E>
+where
+    S: Query<'a>,
+    E: Expression<'a>,
+{
+}
+
+impl<'a, S, G> CompoundQuery<'a> for GroupSelect<S, G>
+where
+    S: Query<'a>,
+    G: Expression<'a>,
+{
+}
+
+impl<'a, S, G> CompoundQuery<'a> for HavingSelect<S, G>
+where
+    S: Query<'a>,
+    G: Expression<'a>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use usql_core::System;
+
+    use super::*;
+    use crate::{
+        expr::{ExpressionExt, val},
+        select::{FilterQuery, Order},
+    };
+
+    #[test]
+    fn test_compound_build() {
+        let query = Select::new("posts", "title")
+            .filter("id".eql(val(1)))
+            .union_all(Select::new("drafts", "title").filter("id".eql(val(2))))
+            .order_by(("title", Order::Asc))
+            .limit(0, 10);
+
+        let mut ctx = Context::new(System::Postgres);
+        Query::build(query, &mut ctx).expect("build");
+
+        assert_eq!(
+            ctx.to_string(),
+            r#"SELECT "title" FROM "posts" WHERE "id" = $1 UNION ALL SELECT "title" FROM "drafts" WHERE "id" = $2 ORDER BY "title" ASC LIMIT 10"#
+        );
+    }
+
+    #[test]
+    fn test_nested_compound_build() {
+        let query =
+            || Select::new("a", "id").except(Select::new("b", "id").union(Select::new("c", "id")));
+
+        let mut ctx = Context::new(System::Postgres);
+        Query::build(query(), &mut ctx).expect("build");
+        assert_eq!(
+            ctx.to_string(),
+            r#"SELECT "id" FROM "a" EXCEPT (SELECT "id" FROM "b" UNION SELECT "id" FROM "c")"#
+        );
+
+        let mut ctx = Context::new(System::Sqlite);
+        let result = Query::build(query(), &mut ctx);
+        assert!(matches!(result, Err(Error::Unsupported { .. })));
+
+        let query = || {
+            Select::new("a", "id")
+                .union(Select::new("b", "id"))
+                .intersect(Select::new("c", "id"))
+        };
+
+        let mut ctx = Context::new(System::Mysql);
+        Query::build(query(), &mut ctx).expect("build");
+        assert_eq!(
+            ctx.to_string(),
+            "(SELECT `id` FROM `a` UNION SELECT `id` FROM `b`) INTERSECT SELECT `id` FROM `c`"
+        );
+
+        let mut ctx = Context::new(System::Sqlite);
+        Query::build(query(), &mut ctx).expect("build");
+        assert_eq!(
+            ctx.to_string(),
+            r#"SELECT "id" FROM "a" UNION SELECT "id" FROM "b" INTERSECT SELECT "id" FROM "c""#
+        );
+    }
+}
@@ -68,8 +68,8 @@ where
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
 pub struct Join<T> {
-    kind: JoinType,
-    table: T,
+    pub(crate) kind: JoinType,
+    pub(crate) table: T,
 }
 
 impl<'val, T> Joinable<'val> for Join<T>
@@ -105,8 +105,8 @@ impl<T> Join<T> {
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
 pub struct JoinOn<T, E> {
-    join: Join<T>,
-    on: E,
+    pub(crate) join: Join<T>,
+    pub(crate) on: E,
 }
 
 impl<'val, T, E> Joinable<'val> for JoinOn<T, E>
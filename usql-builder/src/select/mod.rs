@@ -1,5 +1,6 @@
 mod alias;
 mod apply;
+mod compound;
 mod filter;
 mod group;
 mod having;
@@ -10,8 +11,10 @@ mod select;
 mod selection;
 mod sort;
 mod target;
+mod window;
+mod with;
 
 pub use self::{
-    alias::*, apply::Apply, filter::*, group::*, having::*, join::*, limit::*, query::*, select::*,
-    selection::*, sort::*, target::*,
+    alias::*, apply::Apply, compound::*, filter::*, group::*, having::*, join::*, limit::*,
+    query::*, select::*, selection::*, sort::*, target::*, window::*, with::*,
 };
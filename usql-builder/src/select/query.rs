@@ -8,7 +8,8 @@ use crate::{
     error::Error,
     expr::{Expression, Ident},
     select::{
-        HavingSelect, Select, Selection, SortKey, SortSelect, Target,
+        Alias, Aliased, CompoundSelect, HavingSelect, Select, Selection, SortKey, SortSelect,
+        Target, WindowSelect, With,
         apply::Apply,
         filter::FilterSelect,
         group::GroupSelect,
@@ -105,6 +106,14 @@ pub trait QueryExt<'a>: Query<'a> + Sized {
         QueryStmt::new(self)
     }
 
+    /// Use the query as a derived table, eg. in a join
+    fn alias<A>(self, alias: A) -> Aliased<QueryStmt<Self>, A>
+    where
+        A: Alias<'a>,
+    {
+        Aliased::new(QueryStmt::new(self), alias)
+    }
+
     fn apply<T>(self, apply: T) -> T::Output
     where
         T: Apply<'a, Self>,
@@ -319,6 +328,45 @@ where
     }
 }
 
+// Subqueries
+
+macro_rules! subquery {
+    ($($ty: ident <$($param: ident),*>),*) => {
+        $(
+            impl<'a, $($param),*> Expression<'a> for $ty<$($param),*>
+            where
+                Self: Query<'a>,
+            {
+                fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+                    <QueryStmt<Self> as Expression<'a>>::build(QueryStmt::new(self), ctx)
+                }
+            }
+
+            impl<'a, $($param),*> Selection<'a> for $ty<$($param),*>
+            where
+                Self: Query<'a>,
+            {
+                fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+                    <QueryStmt<Self> as Expression<'a>>::build(QueryStmt::new(self), ctx)
+                }
+            }
+        )*
+    };
+}
+
+subquery!(
+    Select<T, S>,
+    FilterSelect<S, E>,
+    JoinSelect<S, J>,
+    SortSelect<S, O>,
+    LimitSelect<S>,
+    GroupSelect<S, G>,
+    HavingSelect<S, E>,
+    CompoundSelect<L, R>,
+    WindowSelect<S, W>,
+    With<C, Q>
+);
+
 pub trait DynQuery<'a> {
     fn build(self: Box<Self>, ctx: &mut Context<'a>) -> Result<(), Error>;
 }
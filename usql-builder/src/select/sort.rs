@@ -91,6 +91,12 @@ macro_rules! sort_key {
 
 sort_key!(T1, T2, T3, T4, T5, T6, T7, T8);
 
+impl<'val> SortKey<'val> for () {
+    fn build(self, _ctx: &mut Context<'val>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
 impl<'val, S: SortKey<'val>> SortKey<'val> for Vec<S> {
     fn build(self, ctx: &mut Context<'val>) -> Result<(), Error> {
         for (c, v) in self.into_iter().enumerate() {
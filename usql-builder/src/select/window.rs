@@ -0,0 +1,201 @@
+use core::fmt::Write;
+
+use crate::{
+    context::Context,
+    error::Error,
+    expr::{Args, Expression, Ident, Window},
+    select::{
+        CompoundQuery, FilterSelect, GroupSelect, HavingSelect, JoinSelect, Joinable, LimitQuery,
+        Select, Selection, SortKey, SortQuery, Target, query::Query,
+    },
+};
+
+/// A window defined by name in the `WINDOW` clause, so several
+/// functions can share it with `over(name)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NamedWindow<N, W> {
+    name: N,
+    window: W,
+}
+
+pub trait Windows<'a> {
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error>;
+}
+
+impl<'a, N, P, O> Windows<'a> for NamedWindow<N, Window<P, O>>
+where
+    N: Ident<'a>,
+    P: Args<'a>,
+    O: SortKey<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        self.name.build(ctx)?;
+        ctx.write_str(" AS (")?;
+        self.window.build_definition(ctx)?;
+        ctx.write_char(')')?;
+        Ok(())
+    }
+}
+
+impl<'a, L, R> Windows<'a> for (L, R)
+where
+    L: Windows<'a>,
+    R: Windows<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        self.0.build(ctx)?;
+        ctx.write_str(", ")?;
+        self.1.build(ctx)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WindowSelect<S, W> {
+    select: S,
+    windows: W,
+}
+
+impl<S, N, P, O> WindowSelect<S, NamedWindow<N, Window<P, O>>> {
+    pub fn new(select: S, name: N, window: Window<P, O>) -> Self {
+        WindowSelect {
+            select,
+            windows: NamedWindow { name, window },
+        }
+    }
+}
+
+impl<S, W> WindowSelect<S, W> {
+    pub fn window<N, P, O>(
+        self,
+        name: N,
+        window: Window<P, O>,
+    ) -> WindowSelect<S, (W, NamedWindow<N, Window<P, O>>)> {
+        WindowSelect {
+            select: self.select,
+            windows: (self.windows, NamedWindow { name, window }),
+        }
+    }
+}
+
+impl<'a, S, W> Query<'a> for WindowSelect<S, W>
+where
+    S: Query<'a>,
+    W: Windows<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        self.select.build(ctx)?;
+        ctx.write_str(" WINDOW ")?;
+        self.windows.build(ctx)?;
+        Ok(())
+    }
+}
+
+pub trait WindowQuery<'a>: Query<'a> + Sized {
+    fn window<N, P, O>(
+        self,
+        name: N,
+        window: Window<P, O>,
+    ) -> WindowSelect<Self, NamedWindow<N, Window<P, O>>> {
+        WindowSelect::new(self, name, window)
+    }
+}
+
+impl<'a, S, W> SortQuery<'a> for WindowSelect<S, W>
+where
+    S: Query<'a>,
+    W: Windows<'a>,
+{
+}
+
+impl<'a, S, W> LimitQuery<'a> for WindowSelect<S, W>
+where
+    S: Query<'a>,
+    W: Windows<'a>,
+{
+}
+
+impl<'a, S, W> CompoundQuery<'a> for WindowSelect<S, W>
+where
+    S: Query<'a>,
+    W: Windows<'a>,
+{
+}
+
+impl<'a, T, S> WindowQuery<'a> for Select<T, S>
+where
+    T: Target<'a>,
+    S: Selection<'a>,
+{
+}
+
+impl<'a, S, J> WindowQuery<'a> for JoinSelect<S, J>
+where
+    S: Query<'a>,
+    J: Joinable<'a>,
+{
+}
+
+impl<'a, S, E> WindowQuery<'a> for FilterSelect<S, E>
+where
+    S: Query<'a>,
+    E: Expression<'a>,
+{
+}
+
+impl<'a, S, G> WindowQuery<'a> for GroupSelect<S, G>
+where
+    S: Query<'a>,
+    G: Expression<'a>,
+{
+}
+
+impl<'a, S, G> WindowQuery<'a> for HavingSelect<S, G>
+where
+    S: Query<'a>,
+    G: Expression<'a>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use usql_core::System;
+
+    use super::*;
+    use crate::{
+        expr::{Call, FrameBound, row_number, window},
+        select::{IdentExt, Order, QueryExt},
+        statement::StatementExt,
+    };
+
+    #[test]
+    fn test_window_build() {
+        let query = Select::new(
+            "sales",
+            (
+                "region",
+                row_number().over("w").alias("rank"),
+                Call::new("SUM", ("amount",))
+                    .over(
+                        window()
+                            .order_by(("day", Order::Asc))
+                            .rows(FrameBound::UnboundedPreceding, FrameBound::CurrentRow),
+                    )
+                    .alias("total"),
+            ),
+        )
+        .window(
+            "w",
+            window()
+                .partition_by(("region",))
+                .order_by(("amount", Order::Desc)),
+        );
+
+        let stmt = query.into_stmt().to_sql(System::Sqlite).expect("build");
+
+        assert_eq!(
+            stmt.sql,
+            r#"SELECT "region", ROW_NUMBER() OVER "w" AS "rank", SUM("amount") OVER (ORDER BY "day" ASC ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS "total" FROM "sales" WINDOW "w" AS (PARTITION BY "region" ORDER BY "amount" DESC)"#
+        );
+    }
+}
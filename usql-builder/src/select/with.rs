@@ -0,0 +1,239 @@
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::{
+    context::Context,
+    error::Error,
+    expr::Ident,
+    select::{FilterQuery, GroupQuery, JoinQuery, LimitQuery, Select, SortQuery, query::Query},
+};
+
+pub fn with<N, Q>(name: N, query: Q) -> WithClause<Cte<N, Q>> {
+    WithClause {
+        recursive: false,
+        tables: Cte::new(name, query),
+    }
+}
+
+pub fn with_recursive<N, Q>(name: N, query: Q) -> WithClause<Cte<N, Q>> {
+    WithClause {
+        recursive: true,
+        tables: Cte::new(name, query),
+    }
+}
+
+/// A named query in a `WITH` clause.
+///
+/// The name can be used as a `Target` by the main query, and by the query
+/// itself when the clause is recursive.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Cte<N, Q> {
+    name: N,
+    columns: Vec<N>,
+    query: Q,
+}
+
+impl<N, Q> Cte<N, Q> {
+    pub fn new(name: N, query: Q) -> Cte<N, Q> {
+        Cte {
+            name,
+            columns: Vec::new(),
+            query,
+        }
+    }
+}
+
+pub trait CommonTables<'a> {
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error>;
+}
+
+impl<'a, N, Q> CommonTables<'a> for Cte<N, Q>
+where
+    N: Ident<'a>,
+    Q: Query<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        self.name.build(ctx)?;
+
+        if !self.columns.is_empty() {
+            ctx.write_char('(')?;
+            for (idx, column) in self.columns.into_iter().enumerate() {
+                if idx > 0 {
+                    ctx.write_str(", ")?;
+                }
+                column.build(ctx)?;
+            }
+            ctx.write_char(')')?;
+        }
+
+        ctx.write_str(" AS (")?;
+        self.query.build(ctx)?;
+        ctx.write_char(')')?;
+
+        Ok(())
+    }
+}
+
+impl<'a, L, R> CommonTables<'a> for (L, R)
+where
+    L: CommonTables<'a>,
+    R: CommonTables<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        self.0.build(ctx)?;
+        ctx.write_str(", ")?;
+        self.1.build(ctx)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WithClause<C> {
+    recursive: bool,
+    tables: C,
+}
+
+impl<N, Q> WithClause<Cte<N, Q>> {
+    /// Name the columns of the last added table
+    pub fn columns<I>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+    {
+        self.tables.columns.extend(columns);
+        self
+    }
+}
+
+impl<L, N, Q> WithClause<(L, Cte<N, Q>)> {
+    /// Name the columns of the last added table
+    pub fn columns<I>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+    {
+        self.tables.1.columns.extend(columns);
+        self
+    }
+}
+
+impl<C> WithClause<C> {
+    pub fn with<N, Q>(self, name: N, query: Q) -> WithClause<(C, Cte<N, Q>)> {
+        WithClause {
+            recursive: self.recursive,
+            tables: (self.tables, Cte::new(name, query)),
+        }
+    }
+
+    /// Add a table which may refer to itself.
+    ///
+    /// The whole clause becomes `WITH RECURSIVE`, as required by Postgres and MySQL.
+    pub fn with_recursive<N, Q>(self, name: N, query: Q) -> WithClause<(C, Cte<N, Q>)> {
+        WithClause {
+            recursive: true,
+            tables: (self.tables, Cte::new(name, query)),
+        }
+    }
+
+    pub fn query<Q>(self, query: Q) -> With<C, Q> {
+        With {
+            clause: self,
+            query,
+        }
+    }
+
+    pub fn select<T, S>(self, target: T, selection: S) -> With<C, Select<T, S>> {
+        self.query(Select::new(target, selection))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct With<C, Q> {
+    clause: WithClause<C>,
+    query: Q,
+}
+
+impl<'a, C, Q> Query<'a> for With<C, Q>
+where
+    C: CommonTables<'a>,
+    Q: Query<'a>,
+{
+    fn build(self, ctx: &mut Context<'a>) -> Result<(), Error> {
+        if self.clause.recursive {
+            ctx.write_str("WITH RECURSIVE ")?;
+        } else {
+            ctx.write_str("WITH ")?;
+        }
+        self.clause.tables.build(ctx)?;
+        ctx.write_char(' ')?;
+        self.query.build(ctx)?;
+        Ok(())
+    }
+}
+
+// The clauses of the main query come after the WITH clause,
+// so they can be appended to the whole statement
+
+impl<'a, C, Q> FilterQuery<'a> for With<C, Q>
+where
+    C: CommonTables<'a>,
+    Q: FilterQuery<'a>,
+{
+}
+
+impl<'a, C, Q> JoinQuery<'a> for With<C, Q>
+where
+    C: CommonTables<'a>,
+    Q: JoinQuery<'a>,
+{
+}
+
+impl<'a, C, Q> SortQuery<'a> for With<C, Q>
+where
+    C: CommonTables<'a>,
+    Q: SortQuery<'a>,
+{
+}
+
+impl<'a, C, Q> LimitQuery<'a> for With<C, Q>
+where
+    C: CommonTables<'a>,
+    Q: LimitQuery<'a>,
+{
+}
+
+impl<'a, C, Q> GroupQuery<'a> for With<C, Q>
+where
+    C: CommonTables<'a>,
+    Q: GroupQuery<'a>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use usql_core::System;
+
+    use super::*;
+    use crate::{
+        expr::{ExpressionExt, val},
+        select::Star,
+    };
+
+    #[test]
+    fn test_with_build() {
+        let query = with_recursive(
+            "thread",
+            Select::new("comments", Star).filter("id".eql(val(1))),
+        )
+        .columns(["id"])
+        .select("thread", Star)
+        .filter("id".eql(val(2)));
+
+        let mut ctx = Context::new(System::Postgres);
+        Query::build(query, &mut ctx).expect("build");
+
+        assert_eq!(
+            ctx.to_string(),
+            r#"WITH RECURSIVE "thread"("id") AS (SELECT * FROM "comments" WHERE "id" = $1) SELECT * FROM "thread" WHERE "id" = $2"#
+        );
+    }
+}
@@ -0,0 +1,41 @@
+/// Common classification of the errors reported by the databases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    UniqueViolation,
+    ForeignKeyViolation,
+    NotNullViolation,
+    CheckViolation,
+    /// The transaction conflicted with a concurrent one, eg. a serialization failure or deadlock
+    SerializationFailure,
+    /// The database, or a lock on it, is held by someone else
+    Busy,
+    ConnectionLost,
+    Syntax,
+    Other,
+}
+
+/// Classifies a connector's native errors so callers can react to them
+/// without matching on backend specific types.
+pub trait DatabaseError {
+    fn kind(&self) -> ErrorKind;
+
+    /// Name of the violated constraint, when the database reports it
+    fn constraint(&self) -> Option<&str> {
+        None
+    }
+
+    /// Name of the table the error relates to, when the database reports it
+    fn table(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether the failed transaction may succeed when run again,
+    /// eg. serialization failures or a busy database.
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::SerializationFailure | ErrorKind::Busy
+        )
+    }
+}
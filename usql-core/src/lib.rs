@@ -3,11 +3,14 @@
 
 extern crate alloc;
 
+mod error;
+mod options;
+mod result;
 mod system;
 mod traits;
 pub mod util;
 
-pub use self::{system::*, traits::*};
+pub use self::{error::*, options::*, result::*, system::*, traits::*};
 
 pub mod prelude {
     pub use super::traits::*;
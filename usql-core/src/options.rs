@@ -0,0 +1,59 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+/// How SQLite takes its locks when a transaction starts.
+///
+/// `Deferred` waits for the first read or write, so concurrent writers
+/// can fail halfway with `SQLITE_BUSY`; `Immediate` takes the write lock upfront.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TransactionBehavior {
+    #[default]
+    Deferred,
+    Immediate,
+    Exclusive,
+}
+
+/// Options for `Connection::begin_with`.
+///
+/// Connectors map these to their own dialect and ignore what it can't express.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TransactionOptions {
+    pub isolation_level: Option<IsolationLevel>,
+    pub read_only: bool,
+    /// Postgres only, has no effect unless the transaction is also
+    /// serializable and read only
+    pub deferrable: bool,
+    /// SQLite and LibSql only
+    pub behavior: TransactionBehavior,
+}
+
+impl TransactionOptions {
+    pub fn new() -> TransactionOptions {
+        TransactionOptions::default()
+    }
+
+    pub fn isolation_level(mut self, level: IsolationLevel) -> Self {
+        self.isolation_level = Some(level);
+        self
+    }
+
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    pub fn deferrable(mut self, deferrable: bool) -> Self {
+        self.deferrable = deferrable;
+        self
+    }
+
+    pub fn behavior(mut self, behavior: TransactionBehavior) -> Self {
+        self.behavior = behavior;
+        self
+    }
+}
@@ -0,0 +1,14 @@
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct QueryResult {
+    pub affected_rows: u64,
+    pub last_insert_id: Option<i64>,
+}
+
+impl QueryResult {
+    pub fn new(affected_rows: u64, last_insert_id: Option<i64>) -> QueryResult {
+        QueryResult {
+            affected_rows,
+            last_insert_id,
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::system::System;
+use crate::{
+    error::DatabaseError, options::TransactionOptions, result::QueryResult, system::System,
+};
 use alloc::vec::Vec;
 use futures_core::stream::BoxStream;
 use usql_value::{Type, ValueCow};
@@ -9,7 +11,7 @@ pub trait Connector: Send + Sync {
     type Row: Row<Connector = Self>;
     type Info: DatabaseInfo;
     type Pool: Pool<Connector = Self>;
-    type Error;
+    type Error: DatabaseError;
     type Options;
 
     fn create_pool(
@@ -62,7 +64,7 @@ pub trait Executor {
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: Vec<ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a;
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a;
 
     fn exec_batch<'a>(
         &'a self,
@@ -77,10 +79,29 @@ pub trait Connection: Executor + Send + Sync {
 
     fn begin(
         &mut self,
+    ) -> impl Future<Output = Result<Self::Transaction<'_>, <Self::Connector as Connector>::Error>> + Send
+    {
+        self.begin_with(TransactionOptions::default())
+    }
+
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
     ) -> impl Future<Output = Result<Self::Transaction<'_>, <Self::Connector as Connector>::Error>> + Send;
 }
 
 pub trait Transaction<'conn>: Executor + Send + Sync {
+    type Savepoint<'a>: Transaction<'a, Connector = Self::Connector>
+    where
+        Self: 'a;
+
+    /// Start a nested transaction backed by a savepoint.
+    /// Committing the savepoint releases it into the outer transaction,
+    /// rolling it back only undoes the work done since it was created.
+    fn savepoint(
+        &mut self,
+    ) -> impl Future<Output = Result<Self::Savepoint<'_>, <Self::Connector as Connector>::Error>> + Send;
+
     fn commit(
         self,
     ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send;
@@ -1,5 +1,8 @@
 use core::fmt;
+#[cfg(feature = "replication")]
+use std::path::PathBuf;
 
+use usql_core::{DatabaseError, ErrorKind};
 use usql_value::Type;
 
 #[derive(Debug)]
@@ -7,7 +10,16 @@ pub enum Error {
     LibSql(libsql::Error),
     NotFound,
     Pool,
-    Convert { found: Option<Type>, expected: Type },
+    Convert {
+        found: Option<Type>,
+        expected: Type,
+    },
+    /// [`Pool::sync`](crate::Pool::sync) on a pool that isn't an embedded replica
+    #[cfg(feature = "replication")]
+    NotReplica,
+    /// The file an embedded replica syncs from doesn't exist
+    #[cfg(feature = "replication")]
+    PrimaryNotFound(PathBuf),
 }
 
 impl From<libsql::Error> for Error {
@@ -22,6 +34,12 @@ impl fmt::Display for Error {
             Self::LibSql(err) => err.fmt(f),
             Self::NotFound => write!(f, "not found"),
             Self::Pool => write!(f, "pool"),
+            #[cfg(feature = "replication")]
+            Self::NotReplica => write!(f, "not an embedded replica"),
+            #[cfg(feature = "replication")]
+            Self::PrimaryNotFound(path) => {
+                write!(f, "primary not found: {}", path.display())
+            }
             Self::Convert { found, expected } => {
                 write!(
                     f,
@@ -46,6 +64,58 @@ impl core::error::Error for Error {
     }
 }
 
+impl Error {
+    // Constraint messages look like `UNIQUE constraint failed: users.email`
+    fn constraint_detail(&self) -> Option<&str> {
+        match self {
+            Self::LibSql(libsql::Error::SqliteFailure(_, msg)) => msg
+                .split_once("constraint failed: ")
+                .map(|(_, detail)| detail),
+            _ => None,
+        }
+    }
+}
+
+impl DatabaseError for Error {
+    fn kind(&self) -> ErrorKind {
+        let Self::LibSql(libsql::Error::SqliteFailure(code, msg)) = self else {
+            return ErrorKind::Other;
+        };
+
+        // The primary code is in the low byte of extended codes
+        match code & 0xff {
+            // SQLITE_CONSTRAINT
+            19 if msg.starts_with("UNIQUE") => ErrorKind::UniqueViolation,
+            19 if msg.starts_with("FOREIGN KEY") => ErrorKind::ForeignKeyViolation,
+            19 if msg.starts_with("NOT NULL") => ErrorKind::NotNullViolation,
+            19 if msg.starts_with("CHECK") => ErrorKind::CheckViolation,
+            // SQLITE_BUSY and SQLITE_LOCKED
+            5 | 6 => ErrorKind::Busy,
+            // SQLITE_ERROR
+            1 if msg.contains("syntax error") => ErrorKind::Syntax,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    fn constraint(&self) -> Option<&str> {
+        // Only check constraints are reported by name
+        match self.kind() {
+            ErrorKind::CheckViolation => self.constraint_detail(),
+            _ => None,
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        match self.kind() {
+            ErrorKind::UniqueViolation | ErrorKind::NotNullViolation => self
+                .constraint_detail()?
+                .split_once('.')
+                .map(|(table, _)| table),
+            _ => None,
+        }
+    }
+}
+
 impl From<deadpool::managed::PoolError<Error>> for Error {
     fn from(value: deadpool::managed::PoolError<Error>) -> Self {
         match value {
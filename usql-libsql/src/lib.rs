@@ -1,12 +1,15 @@
-// mod conn;
 mod connector;
 mod error;
 mod pool;
+#[cfg(feature = "replication")]
+mod replica;
 mod row;
 mod stmt;
 mod transaction;
-// mod value;
+mod util;
 
+#[cfg(feature = "replication")]
+pub use self::pool::ReplicaOptions;
 pub use self::{
     connector::*,
     error::Error,
@@ -15,3 +18,5 @@ pub use self::{
     stmt::Stmt,
     transaction::Trans,
 };
+
+pub use libsql::OpenFlags;
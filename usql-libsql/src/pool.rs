@@ -1,50 +1,150 @@
-use async_lock::Mutex;
+use async_lock::{Mutex, OnceCell};
 use libsql::Connection;
 use std::path::{Path, PathBuf};
+#[cfg(feature = "replication")]
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
-use crate::{row::Row, stmt::Stmt, transaction::Trans};
-use usql_core::{Connector, Executor, QueryStream};
+#[cfg(feature = "replication")]
+use crate::replica::sync_file;
+use crate::{error::Error, row::Row, stmt::Stmt, transaction::Trans, util::into_params};
+use usql_core::{
+    Connector, Executor, QueryResult, QueryStream, TransactionBehavior, TransactionOptions,
+};
+use usql_sqlite_util::last_insert_id;
 use usql_value::ValueCow;
 
 use super::{LibSqlInfo, connector::LibSql};
 
-// pub type PooledConn = deadpool::managed::Object<Manager>;
-
 #[derive(Debug)]
 enum Source {
     Memory(Uuid),
     Path(PathBuf),
+    #[cfg(feature = "replication")]
+    RemoteReplica(PathBuf, ReplicaOptions),
+    #[cfg(feature = "replication")]
+    FileReplica {
+        path: PathBuf,
+        primary: PathBuf,
+        sync_interval: Option<Duration>,
+    },
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct ManagerOptions {
     pub path: Option<PathBuf>,
     pub flags: libsql::OpenFlags,
+    /// Keep the database at `path` as an embedded replica of another database
+    #[cfg(feature = "replication")]
+    pub replica: Option<ReplicaOptions>,
+}
+
+impl ManagerOptions {
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn flags(mut self, flags: libsql::OpenFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    #[cfg(feature = "replication")]
+    pub fn replica(mut self, path: impl Into<PathBuf>, replica: ReplicaOptions) -> Self {
+        self.path = Some(path.into());
+        self.replica = Some(replica);
+        self
+    }
+}
+
+/// The primary an embedded replica syncs from.
+///
+/// Primaries served over the network (`libsql://`, `http(s)://` or `ws(s)://`
+/// urls, e.g. sqld or Turso) are replicated by libSQL, which forwards writes
+/// on the replica to the primary.
+///
+/// Any other url is the path of a local database file, optionally prefixed
+/// with `file:`. libSQL can't replicate from those, so the replica is a copy of
+/// the primary taken when the pool opens, on [`Pool::sync`](crate::Pool::sync)
+/// and, with a `sync_interval`, when a connection is handed out after the
+/// interval has passed. Writes to such a replica are lost on the next sync,
+/// write to the primary instead. Syncing fails with [`Error::PrimaryNotFound`]
+/// when the file doesn't exist.
+#[cfg(feature = "replication")]
+#[derive(Debug, Clone)]
+pub struct ReplicaOptions {
+    pub url: String,
+    pub auth_token: String,
+    pub sync_interval: Option<Duration>,
+}
+
+#[cfg(feature = "replication")]
+impl ReplicaOptions {
+    pub fn new(url: impl Into<String>, auth_token: impl Into<String>) -> ReplicaOptions {
+        ReplicaOptions {
+            url: url.into(),
+            auth_token: auth_token.into(),
+            sync_interval: None,
+        }
+    }
+
+    pub fn sync_interval(mut self, interval: Duration) -> Self {
+        self.sync_interval = Some(interval);
+        self
+    }
+
+    fn is_remote(&self) -> bool {
+        ["libsql://", "http://", "https://", "ws://", "wss://"]
+            .iter()
+            .any(|scheme| self.url.starts_with(scheme))
+    }
+
+    fn file_primary(&self) -> Option<PathBuf> {
+        if self.is_remote() {
+            return None;
+        }
+
+        let path = self.url.strip_prefix("file:").unwrap_or(&self.url);
+        Some(PathBuf::from(path))
+    }
 }
 
 pub struct Manager {
     source: Source,
     flags: libsql::OpenFlags,
+    db: OnceCell<libsql::Database>,
     _persist: Mutex<Option<libsql::Connection>>,
+    // When a file replica last synced, also keeps two syncs from running at once
+    #[cfg(feature = "replication")]
+    last_sync: Mutex<Option<Instant>>,
 }
 
 impl Manager {
     pub fn file(path: impl AsRef<Path>) -> Manager {
-        Manager::new(ManagerOptions {
-            path: Some(path.as_ref().to_path_buf()),
-            flags: libsql::OpenFlags::default(),
-        })
+        Manager::new(ManagerOptions::default().path(path.as_ref()))
     }
 
     pub fn memory() -> Manager {
-        Manager::new(ManagerOptions {
-            path: None,
-            flags: libsql::OpenFlags::default(),
-        })
+        Manager::new(ManagerOptions::default())
     }
 
     pub fn new(options: ManagerOptions) -> Manager {
+        #[cfg(feature = "replication")]
+        let source = match (options.path, options.replica) {
+            (Some(path), Some(replica)) => match replica.file_primary() {
+                Some(primary) => Source::FileReplica {
+                    path,
+                    primary,
+                    sync_interval: replica.sync_interval,
+                },
+                None => Source::RemoteReplica(path, replica),
+            },
+            (Some(path), None) => Source::Path(path),
+            (None, _) => Source::Memory(Uuid::new_v4()),
+        };
+
+        #[cfg(not(feature = "replication"))]
         let source = match options.path {
             Some(path) => Source::Path(path),
             None => Source::Memory(Uuid::new_v4()),
@@ -53,52 +153,127 @@ impl Manager {
         Manager {
             source,
             flags: options.flags,
+            db: OnceCell::new(),
             _persist: Mutex::new(None),
+            #[cfg(feature = "replication")]
+            last_sync: Mutex::new(None),
         }
     }
+
+    // All connections in the pool share one database handle,
+    // replicas need it to sync
+    async fn database(&self) -> Result<&libsql::Database, Error> {
+        self.db
+            .get_or_try_init(|| async {
+                let db = match &self.source {
+                    Source::Path(path) => {
+                        libsql::Builder::new_local(path)
+                            .flags(self.flags)
+                            .build()
+                            .await?
+                    }
+                    Source::Memory(id) => {
+                        libsql::Builder::new_local(format!("file:{}?mode=memory&cache=shared", id))
+                            .flags(self.flags)
+                            .build()
+                            .await?
+                    }
+                    #[cfg(feature = "replication")]
+                    Source::RemoteReplica(path, replica) => {
+                        let mut builder = libsql::Builder::new_remote_replica(
+                            path,
+                            replica.url.clone(),
+                            replica.auth_token.clone(),
+                        );
+
+                        if let Some(interval) = replica.sync_interval {
+                            builder = builder.sync_interval(interval);
+                        }
+
+                        let db = builder.build().await?;
+                        db.sync().await?;
+                        db
+                    }
+                    #[cfg(feature = "replication")]
+                    Source::FileReplica { path, primary, .. } => {
+                        let db = libsql::Builder::new_local(path)
+                            .flags(self.flags)
+                            .build()
+                            .await?;
+                        sync_file(&db, primary).await?;
+                        *self.last_sync.lock().await = Some(Instant::now());
+                        db
+                    }
+                };
+
+                Ok(db)
+            })
+            .await
+    }
+}
+
+#[cfg(feature = "replication")]
+impl Manager {
+    async fn sync(&self) -> Result<(), Error> {
+        match &self.source {
+            Source::RemoteReplica(..) => {
+                self.database().await?.sync().await?;
+            }
+            Source::FileReplica { primary, .. } => {
+                let db = self.database().await?;
+                let mut last_sync = self.last_sync.lock().await;
+                sync_file(db, primary).await?;
+                *last_sync = Some(Instant::now());
+            }
+            _ => return Err(Error::NotReplica),
+        }
+
+        Ok(())
+    }
+
+    // libSQL syncs remote replicas in the background, file replicas
+    // are synced here when a connection is handed out
+    async fn sync_due(&self) -> Result<(), Error> {
+        let Source::FileReplica {
+            primary,
+            sync_interval: Some(interval),
+            ..
+        } = &self.source
+        else {
+            return Ok(());
+        };
+
+        let db = self.database().await?;
+        let mut last_sync = self.last_sync.lock().await;
+        if last_sync.is_some_and(|last| last.elapsed() < *interval) {
+            return Ok(());
+        }
+
+        sync_file(db, primary).await?;
+        *last_sync = Some(Instant::now());
+
+        Ok(())
+    }
 }
 
 impl deadpool::managed::Manager for Manager {
     type Type = Connection;
 
-    type Error = super::error::Error;
+    type Error = Error;
 
     fn create(&self) -> impl Future<Output = Result<Self::Type, Self::Error>> + Send {
         async move {
-            match &self.source {
-                Source::Path(path) => {
-                    //
-                    let db = libsql::Builder::new_local(path)
-                        .flags(self.flags)
-                        .build()
-                        .await?;
-                    Ok(db.connect()?)
-                }
-                Source::Memory(id) => {
-                    // let connection =
-                    //     || Conn::open(format!("file:{}?mode=memory&cache=shared", id), self.flags);
-
-                    let connection = || async move {
-                        let db = libsql::Builder::new_local(format!(
-                            "file:{}?mode=memory&cache=shared",
-                            id
-                        ))
-                        .flags(self.flags)
-                        .build()
-                        .await?;
-                        db.connect()
-                    };
-
-                    {
-                        let mut persist = self._persist.lock().await;
-                        if persist.is_none() {
-                            *persist = Some(connection().await?);
-                        }
-                    }
+            let db = self.database().await?;
 
-                    Ok(connection().await?)
+            // An in-memory database lives as long as one connection to it is open
+            if let Source::Memory(_) = &self.source {
+                let mut persist = self._persist.lock().await;
+                if persist.is_none() {
+                    *persist = Some(db.connect()?);
                 }
             }
+
+            Ok(db.connect()?)
         }
     }
 
@@ -118,6 +293,12 @@ impl Pool {
     pub fn new(manager: Manager) -> Pool {
         Pool(deadpool::managed::Pool::builder(manager).build().unwrap())
     }
+
+    /// Pull the latest changes from the primary into the embedded replica
+    #[cfg(feature = "replication")]
+    pub async fn sync(&self) -> Result<(), Error> {
+        self.0.manager().sync().await
+    }
 }
 
 impl usql_core::Pool for Pool {
@@ -132,22 +313,34 @@ impl usql_core::Pool for Pool {
         >,
     > + Send
     + '_ {
-        async move { Ok(Conn(self.0.get().await?)) }
+        async move {
+            #[cfg(feature = "replication")]
+            self.0.manager().sync_due().await?;
+
+            Ok(Conn(self.0.get().await?))
+        }
     }
 }
 
 pub struct Conn(deadpool::managed::Object<Manager>);
 
 impl usql_core::Connection for Conn {
-    type Transaction<'conn> = Trans;
+    type Transaction<'conn> = Trans<'conn>;
 
-    fn begin(
+    fn begin_with(
         &mut self,
+        options: TransactionOptions,
     ) -> impl Future<Output = Result<Self::Transaction<'_>, <Self::Connector as Connector>::Error>> + Send
     {
         async move {
-            let conn = self.0.transaction().await?;
-            Ok(Trans(conn))
+            let behavior = match (options.read_only, options.behavior) {
+                (true, _) => libsql::TransactionBehavior::ReadOnly,
+                (false, TransactionBehavior::Deferred) => libsql::TransactionBehavior::Deferred,
+                (false, TransactionBehavior::Immediate) => libsql::TransactionBehavior::Immediate,
+                (false, TransactionBehavior::Exclusive) => libsql::TransactionBehavior::Exclusive,
+            };
+            let conn = self.0.transaction_with_behavior(behavior).await?;
+            Ok(Trans::new(conn))
         }
     }
 }
@@ -169,7 +362,7 @@ impl Executor for Conn {
         >,
     > + Send
     + 'a {
-        async move { Ok(Stmt(self.0.prepare(query).await?)) }
+        async move { Ok(Stmt::new(self.0.prepare(query).await?, query)) }
     }
 
     fn query<'a>(
@@ -178,7 +371,8 @@ impl Executor for Conn {
         params: std::vec::Vec<ValueCow<'a>>,
     ) -> QueryStream<'a, Self::Connector> {
         let stream = async_stream::try_stream! {
-            let mut rows = stmt.0.query(params).await?;
+            stmt.0.reset();
+            let mut rows = stmt.0.query(into_params(params)?).await?;
 
             while let Some(next) = rows.next().await? {
                 yield Row(next);
@@ -192,10 +386,13 @@ impl Executor for Conn {
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: std::vec::Vec<ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
         async move {
-            stmt.0.execute(params).await?;
-            Ok(())
+            stmt.0.reset();
+            let affected_rows = stmt.0.execute(into_params(params)?).await? as u64;
+            let last_insert_id = last_insert_id(&stmt.1, affected_rows, self.0.last_insert_rowid());
+            Ok(QueryResult::new(affected_rows, last_insert_id))
         }
     }
 
@@ -209,3 +406,73 @@ impl Executor for Conn {
         }
     }
 }
+
+#[cfg(all(test, feature = "replication"))]
+mod tests {
+    use std::time::Duration;
+
+    use usql_core::Pool as _;
+
+    use super::{Error, Manager, ManagerOptions, Pool, ReplicaOptions, Source};
+
+    #[test]
+    fn test_replica_options() {
+        let manager = Manager::new(
+            ManagerOptions::default().replica(
+                "replica.db",
+                ReplicaOptions::new("libsql://db.example.com", "token")
+                    .sync_interval(Duration::from_secs(5)),
+            ),
+        );
+
+        let Source::RemoteReplica(path, replica) = &manager.source else {
+            panic!("expected a remote replica, got {:?}", manager.source);
+        };
+        assert_eq!(path.to_str(), Some("replica.db"));
+        assert_eq!(replica.url, "libsql://db.example.com");
+        assert_eq!(replica.auth_token, "token");
+        assert_eq!(replica.sync_interval, Some(Duration::from_secs(5)));
+
+        let manager = Manager::new(ManagerOptions::default().path("local.db"));
+        assert!(matches!(manager.source, Source::Path(_)));
+    }
+
+    #[test]
+    fn test_file_replica_options() {
+        let manager = Manager::new(
+            ManagerOptions::default()
+                .replica("replica.db", ReplicaOptions::new("file:primary.db", "")),
+        );
+
+        let Source::FileReplica {
+            path,
+            primary,
+            sync_interval,
+        } = &manager.source
+        else {
+            panic!("expected a file replica, got {:?}", manager.source);
+        };
+        assert_eq!(path.to_str(), Some("replica.db"));
+        assert_eq!(primary.to_str(), Some("primary.db"));
+        assert_eq!(*sync_interval, None);
+    }
+
+    #[tokio::test]
+    async fn test_missing_primary() {
+        let pool = Pool::new(Manager::new(ManagerOptions::default().replica(
+            std::env::temp_dir().join("usql-libsql-replica.db"),
+            ReplicaOptions::new("file:usql-libsql-missing-primary.db", ""),
+        )));
+
+        let err = pool.get().await.err().expect("missing primary");
+        assert!(
+            matches!(err, Error::PrimaryNotFound(path) if path.to_str() == Some("usql-libsql-missing-primary.db"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_requires_replica() {
+        let pool = Pool::new(Manager::memory());
+        assert!(matches!(pool.sync().await, Err(Error::NotReplica)));
+    }
+}
@@ -0,0 +1,117 @@
+use std::path::Path;
+
+use crate::error::Error;
+
+/// Copies the file-backed primary at `primary` into the replica `db`.
+///
+/// Runs on a connection of its own, in a single transaction, so the pool's
+/// connections see either the old or the new contents of the replica.
+pub(crate) async fn sync_file(db: &libsql::Database, primary: &Path) -> Result<(), Error> {
+    if !primary.is_file() {
+        return Err(Error::PrimaryNotFound(primary.to_path_buf()));
+    }
+
+    let conn = db.connect()?;
+
+    // Tables are dropped and filled in any order
+    conn.execute("PRAGMA foreign_keys = OFF", ()).await?;
+    conn.execute(
+        "ATTACH DATABASE ?1 AS usql_primary",
+        vec![libsql::Value::Text(primary.to_string_lossy().into_owned())],
+    )
+    .await?;
+
+    let trans = conn
+        .transaction_with_behavior(libsql::TransactionBehavior::Immediate)
+        .await?;
+    if let Err(err) = copy(&trans).await {
+        let _ = trans.rollback().await;
+        return Err(err);
+    }
+    trans.commit().await?;
+
+    conn.execute("DETACH DATABASE usql_primary", ()).await?;
+
+    Ok(())
+}
+
+async fn copy(conn: &libsql::Connection) -> Result<(), Error> {
+    // Indexes and triggers go with their tables
+    for ty in ["view", "table"] {
+        for (_, name, _) in schema(conn, "main", &format!("type = '{ty}'")).await? {
+            conn.execute(&format!("DROP {ty} main.{}", quote(&name)), ())
+                .await?;
+        }
+    }
+
+    let objects = schema(conn, "usql_primary", "sql IS NOT NULL").await?;
+
+    // Rows are copied before the indexes and triggers are created, so copying
+    // neither fires triggers nor updates the indexes row by row
+    for (_, _, sql) in objects.iter().filter(|(ty, ..)| ty == "table") {
+        conn.execute(sql, ()).await?;
+    }
+
+    for (_, name, _) in objects.iter().filter(|(ty, ..)| ty == "table") {
+        let name = quote(name);
+        conn.execute(
+            &format!("INSERT INTO main.{name} SELECT * FROM usql_primary.{name}"),
+            (),
+        )
+        .await?;
+    }
+
+    // Only there when the primary has AUTOINCREMENT tables, which created it in
+    // the replica too
+    let sequence = conn
+        .query(
+            "SELECT 1 FROM usql_primary.sqlite_schema WHERE name = 'sqlite_sequence'",
+            (),
+        )
+        .await?
+        .next()
+        .await?
+        .is_some();
+    if sequence {
+        conn.execute_batch(
+            "DELETE FROM main.sqlite_sequence;
+            INSERT INTO main.sqlite_sequence SELECT * FROM usql_primary.sqlite_sequence;",
+        )
+        .await?;
+    }
+
+    for (_, _, sql) in objects.iter().filter(|(ty, ..)| ty != "table") {
+        conn.execute(sql, ()).await?;
+    }
+
+    Ok(())
+}
+
+// Type, name and sql of the user defined objects in `db`, in the order they were created
+async fn schema(
+    conn: &libsql::Connection,
+    db: &str,
+    filter: &str,
+) -> Result<Vec<(String, String, String)>, Error> {
+    let mut rows = conn
+        .query(
+            &format!(
+                "SELECT type, name, coalesce(sql, '') FROM {db}.sqlite_schema
+                WHERE {filter} AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\'
+                ORDER BY rowid"
+            ),
+            (),
+        )
+        .await?;
+
+    let mut objects = Vec::new();
+    while let Some(row) = rows.next().await? {
+        objects.push((row.get(0)?, row.get(1)?, row.get(2)?));
+    }
+
+    Ok(objects)
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
@@ -2,7 +2,7 @@ use crate::Error;
 
 use super::connector::LibSql;
 use usql_core::Connector;
-use usql_value::{JsonValue, Type, Value, ValueCow, ValueRef, chrono};
+use usql_value::{JsonValue, Type, Value, ValueCow, ValueRef, chrono, geob::Geob};
 
 fn row_index(row: &libsql::Row, name: &str) -> Option<i32> {
     for idx in 0..row.column_count() {
@@ -20,7 +20,7 @@ fn value_ref<'a>(value: libsql::Value) -> ValueCow<'a> {
         libsql::Value::Null => ValueCow::Owned(Value::Null),
         libsql::Value::Integer(i) => ValueCow::Owned(Value::BigInt(i)),
         libsql::Value::Real(i) => ValueCow::Owned(Value::Double(i.into())),
-        libsql::Value::Text(i) => ValueCow::Owned(Value::Text(i)),
+        libsql::Value::Text(i) => ValueCow::Owned(Value::Text(i.into())),
         libsql::Value::Blob(items) => ValueCow::Owned(Value::ByteArray(items.into())),
     }
 }
@@ -55,7 +55,7 @@ impl usql_core::Row for Row {
         index: usql_core::ColumnIndex<'_>,
         ty: Type,
     ) -> Result<ValueCow<'a>, <Self::Connector as Connector>::Error> {
-        let value = self.get(index)?;
+        let value = usql_core::Row::get(self, index)?;
         get_typed(value, ty)
     }
 
@@ -173,19 +173,19 @@ fn get_typed<'a>(value: ValueCow<'a>, ty: Type) -> Result<ValueCow<'a>, Error> {
                 });
             }
         },
-        Type::Real => match value.as_ref() {
-            ValueRef::Float(f) => Value::Double((*f as f64).into()).into(),
-            ValueRef::Double(f) => Value::Double(f).into(),
+        Type::Float => match value.as_ref() {
+            ValueRef::Float(f) => Value::Float(f).into(),
+            ValueRef::Double(f) => Value::Float((*f as f32).into()).into(),
             _ => {
                 return Err(Error::Convert {
                     found: value.as_ref().get_type(),
-                    expected: Type::Real,
+                    expected: Type::Float,
                 });
             }
         },
         Type::Double => match value.as_ref() {
-            ValueRef::Float(f) => Value::Float((*f as f32).into()).into(),
-            ValueRef::Double(f) => Value::Float((*f as f32).into()).into(),
+            ValueRef::Float(f) => Value::Double((*f as f64).into()).into(),
+            ValueRef::Double(f) => Value::Double(f).into(),
             _ => {
                 return Err(Error::Convert {
                     found: value.as_ref().get_type(),
@@ -235,6 +235,29 @@ fn get_typed<'a>(value: ValueCow<'a>, ty: Type) -> Result<ValueCow<'a>, Error> {
                 }
             }
         }
+        Type::Geometry(geo_ty) => match value.as_ref() {
+            ValueRef::Text(text) => {
+                let geo = Geob::from_text(text).map_err(|_| Error::Convert {
+                    found: Some(Type::Text),
+                    expected: Type::Geometry(geo_ty),
+                })?;
+
+                if !geo.is(geo_ty) {
+                    return Err(Error::Convert {
+                        found: Some(Type::Geometry(geo.as_ref().ty())),
+                        expected: Type::Geometry(geo_ty),
+                    });
+                }
+
+                Value::Geometry(geo).into()
+            }
+            v => {
+                return Err(Error::Convert {
+                    found: v.get_type(),
+                    expected: Type::Geometry(geo_ty),
+                });
+            }
+        },
         Type::Any => value,
     };
 
@@ -2,7 +2,14 @@ use usql_core::Statement;
 
 use crate::LibSql;
 
-pub struct Stmt(pub libsql::Statement);
+pub struct Stmt(pub libsql::Statement, pub(crate) String);
+
+impl Stmt {
+    // The sql is kept to decide whether `last_insert_rowid` belongs to the statement
+    pub(crate) fn new(stmt: libsql::Statement, sql: &str) -> Stmt {
+        Stmt(stmt, sql.into())
+    }
+}
 
 impl Statement for Stmt {
     type Connector = LibSql;
@@ -1,28 +1,164 @@
-use core::ops::Deref;
+use core::marker::PhantomData;
+use std::{
+    string::String,
+    sync::{Arc, Mutex},
+    vec::Vec,
+};
 
-use crate::{row::Row, stmt::Stmt};
-use usql_core::{Connector, Executor, Transaction};
+use crate::{error::Error, row::Row, stmt::Stmt, util::into_params};
+use usql_core::{Connector, Executor, QueryResult, Transaction};
+use usql_sqlite_util::last_insert_id;
 use usql_value::ValueCow;
 
 use super::{LibSql, LibSqlInfo};
 
-pub struct Trans(pub libsql::Transaction);
+/// A transaction, or a savepoint nested inside one.
+///
+/// A savepoint borrows the transaction it was created from, so the outer
+/// scope can't be used or finished until the savepoint is done with.
+pub struct Trans<'conn> {
+    scope: Scope<'conn>,
+    depth: usize,
+    // Rollbacks queued by savepoints dropped without being finished,
+    // they run before the next command in the transaction
+    pending: Arc<Mutex<Vec<String>>>,
+}
+
+enum Scope<'conn> {
+    Transaction(libsql::Transaction, PhantomData<&'conn mut ()>),
+    Savepoint(Savepoint<'conn>),
+}
+
+struct Savepoint<'conn> {
+    conn: &'conn libsql::Connection,
+    name: String,
+    done: bool,
+    pending: Arc<Mutex<Vec<String>>>,
+}
+
+impl Savepoint<'_> {
+    async fn finish(mut self, stmt: String) -> Result<(), Error> {
+        self.conn.execute_batch(&stmt).await?;
+        self.done = true;
+        Ok(())
+    }
+
+    fn rollback_stmt(&self) -> String {
+        format!(
+            "ROLLBACK TO SAVEPOINT {name}; RELEASE SAVEPOINT {name}",
+            name = self.name
+        )
+    }
+}
+
+impl Drop for Savepoint<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            let stmt = self.rollback_stmt();
+            self.pending.lock().expect("pending").push(stmt);
+        }
+    }
+}
+
+impl<'conn> Trans<'conn> {
+    pub(crate) fn new(trans: libsql::Transaction) -> Trans<'conn> {
+        Trans {
+            scope: Scope::Transaction(trans, PhantomData),
+            depth: 0,
+            pending: Default::default(),
+        }
+    }
+
+    fn conn(&self) -> &libsql::Connection {
+        match &self.scope {
+            Scope::Transaction(trans, _) => trans,
+            Scope::Savepoint(savepoint) => savepoint.conn,
+        }
+    }
+
+    async fn flush(&self) -> Result<(), Error> {
+        let pending = core::mem::take(&mut *self.pending.lock().expect("pending"));
+        for stmt in pending {
+            self.conn().execute_batch(&stmt).await?;
+        }
+        Ok(())
+    }
+
+    /// Start a nested transaction, backed by a savepoint, inside this one.
+    pub async fn savepoint(&mut self) -> Result<Trans<'_>, Error> {
+        self.flush().await?;
+
+        let depth = self.depth + 1;
+        let name = format!("usql_sp_{depth}");
+        self.conn()
+            .execute_batch(&format!("SAVEPOINT {name}"))
+            .await?;
+
+        Ok(Trans {
+            scope: Scope::Savepoint(Savepoint {
+                conn: self.conn(),
+                name,
+                done: false,
+                pending: self.pending.clone(),
+            }),
+            depth,
+            pending: self.pending.clone(),
+        })
+    }
+
+    pub async fn commit(self) -> Result<(), Error> {
+        self.flush().await?;
+        match self.scope {
+            Scope::Transaction(trans, _) => trans.commit().await?,
+            Scope::Savepoint(savepoint) => {
+                let stmt = format!("RELEASE SAVEPOINT {}", savepoint.name);
+                savepoint.finish(stmt).await?
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn rollback(self) -> Result<(), Error> {
+        // Pending rollbacks are covered by rolling back this scope
+        self.pending.lock().expect("pending").clear();
+        match self.scope {
+            Scope::Transaction(trans, _) => trans.rollback().await?,
+            Scope::Savepoint(savepoint) => {
+                let stmt = savepoint.rollback_stmt();
+                savepoint.finish(stmt).await?
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'conn> Transaction<'conn> for Trans<'conn> {
+    type Savepoint<'a>
+        = Trans<'a>
+    where
+        Self: 'a;
+
+    fn savepoint(
+        &mut self,
+    ) -> impl Future<Output = Result<Self::Savepoint<'_>, <Self::Connector as Connector>::Error>> + Send
+    {
+        self.savepoint()
+    }
 
-impl Transaction<'_> for Trans {
     fn commit(
         self,
     ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send {
-        async move { Ok(self.0.commit().await?) }
+        self.commit()
     }
 
     fn rollback(
         self,
     ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send {
-        async move { Ok(self.0.rollback().await?) }
+        self.rollback()
     }
 }
 
-impl Executor for Trans {
+impl Executor for Trans<'_> {
     type Connector = LibSql;
 
     fn db_info(&self) -> <Self::Connector as Connector>::Info {
@@ -39,7 +175,10 @@ impl Executor for Trans {
         >,
     > + Send
     + 'a {
-        async move { Ok(Stmt(self.0.deref().prepare(query).await?)) }
+        async move {
+            self.flush().await?;
+            Ok(Stmt::new(self.conn().prepare(query).await?, query))
+        }
     }
 
     fn query<'a>(
@@ -48,7 +187,9 @@ impl Executor for Trans {
         params: std::vec::Vec<ValueCow<'a>>,
     ) -> usql_core::QueryStream<'a, Self::Connector> {
         let stream = async_stream::try_stream! {
-            let mut rows = stmt.0.query(params).await?;
+            self.flush().await?;
+            stmt.0.reset();
+            let mut rows = stmt.0.query(into_params(params)?).await?;
 
             while let Some(next) = rows.next().await? {
                 yield Row(next);
@@ -62,10 +203,15 @@ impl Executor for Trans {
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: std::vec::Vec<ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
         async move {
-            stmt.0.execute(params).await?;
-            Ok(())
+            self.flush().await?;
+            stmt.0.reset();
+            let affected_rows = stmt.0.execute(into_params(params)?).await? as u64;
+            let last_insert_id =
+                last_insert_id(&stmt.1, affected_rows, self.conn().last_insert_rowid());
+            Ok(QueryResult::new(affected_rows, last_insert_id))
         }
     }
 
@@ -74,7 +220,8 @@ impl Executor for Trans {
         stmt: &'a str,
     ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
         async move {
-            self.0.execute_batch(stmt).await?;
+            self.flush().await?;
+            self.conn().execute_batch(stmt).await?;
             Ok(())
         }
     }
@@ -0,0 +1,6 @@
+use libsql::params::IntoValue;
+use usql_value::ValueCow;
+
+pub fn into_params(params: Vec<ValueCow<'_>>) -> Result<Vec<libsql::Value>, libsql::Error> {
+    params.into_iter().map(IntoValue::into_value).collect()
+}
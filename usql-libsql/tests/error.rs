@@ -0,0 +1,99 @@
+use usql_core::{Connection, Connector, DatabaseError, ErrorKind, Executor, Pool, Transaction};
+use usql_libsql::{Error as LibSqlError, LibSql, Options, Pool as LibSqlPool};
+
+const SCHEMA: &str = "
+    CREATE TABLE users (
+        id INTEGER PRIMARY KEY,
+        email TEXT NOT NULL UNIQUE,
+        age INTEGER CONSTRAINT positive_age CHECK (age > 0)
+    );
+    CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users (id));
+    INSERT INTO users (id, email, age) VALUES (1, 'ada@example.com', 36);
+";
+
+async fn pool() -> LibSqlPool {
+    let pool = LibSql::create_pool(Options::default()).await.unwrap();
+    pool.get().await.unwrap().exec_batch(SCHEMA).await.unwrap();
+    pool
+}
+
+async fn fail<E: Executor<Connector = LibSql>>(exec: &E, sql: &str) -> LibSqlError {
+    exec.exec_batch(sql).await.expect_err(sql)
+}
+
+#[tokio::test]
+async fn constraint_violations() {
+    let pool = pool().await;
+    let conn = pool.get().await.unwrap();
+    // Off by default, and set per connection
+    conn.exec_batch("PRAGMA foreign_keys = ON").await.unwrap();
+
+    let err = fail(
+        &conn,
+        "INSERT INTO users (email, age) VALUES ('ada@example.com', 20)",
+    )
+    .await;
+    assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+    assert_eq!(err.table(), Some("users"));
+    assert_eq!(err.constraint(), None);
+    assert!(!err.is_retryable());
+
+    let err = fail(
+        &conn,
+        "INSERT INTO users (id, email) VALUES (1, 'grace@example.com')",
+    )
+    .await;
+    assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+    assert_eq!(err.table(), Some("users"));
+
+    let err = fail(&conn, "INSERT INTO posts (user_id) VALUES (2)").await;
+    assert_eq!(err.kind(), ErrorKind::ForeignKeyViolation);
+    // SQLite doesn't say which key failed
+    assert_eq!(err.table(), None);
+    assert_eq!(err.constraint(), None);
+    assert!(!err.is_retryable());
+
+    let err = fail(&conn, "INSERT INTO users (email) VALUES (NULL)").await;
+    assert_eq!(err.kind(), ErrorKind::NotNullViolation);
+    assert_eq!(err.table(), Some("users"));
+    assert!(!err.is_retryable());
+
+    let err = fail(
+        &conn,
+        "INSERT INTO users (email, age) VALUES ('grace@example.com', -1)",
+    )
+    .await;
+    assert_eq!(err.kind(), ErrorKind::CheckViolation);
+    assert_eq!(err.constraint(), Some("positive_age"));
+    assert_eq!(err.table(), None);
+    assert!(!err.is_retryable());
+
+    let err = fail(&conn, "SELEC 1").await;
+    assert_eq!(err.kind(), ErrorKind::Syntax);
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn locked() {
+    let pool = pool().await;
+    let mut writer = pool.get().await.unwrap();
+    let other = pool.get().await.unwrap();
+
+    let trans = writer.begin().await.unwrap();
+    trans
+        .exec_batch("INSERT INTO users (email) VALUES ('grace@example.com')")
+        .await
+        .unwrap();
+
+    // The in-memory database is shared between the pool's connections,
+    // which lock each other out of tables being written to
+    let err = fail(
+        &other,
+        "INSERT INTO users (email) VALUES ('linus@example.com')",
+    )
+    .await;
+    assert_eq!(err.kind(), ErrorKind::Busy);
+    assert!(err.is_retryable());
+
+    trans.rollback().await.unwrap();
+}
@@ -0,0 +1,169 @@
+//! Embedded replica tests.
+//!
+//! Replicas of a local file run everywhere. Those against a running libSQL
+//! server (sqld or Turso) are ignored by default, run them with the primary's
+//! url and, when it needs one, an auth token:
+//!
+//! ```sh
+//! USQL_LIBSQL_PRIMARY_URL=http://127.0.0.1:8080 USQL_LIBSQL_AUTH_TOKEN=... \
+//!     cargo test -p usql-libsql --features replication -- --ignored
+//! ```
+
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use usql_core::{ColumnIndex, Connector, Executor, Pool as _, Row, util::next};
+use usql_libsql::{LibSql, Options, Pool, ReplicaOptions};
+use usql_value::Value;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("usql-libsql-{}-{name}.db", std::process::id()))
+}
+
+struct Replica {
+    path: PathBuf,
+    pool: Pool,
+}
+
+impl Replica {
+    async fn open(name: &str) -> Replica {
+        let url = std::env::var("USQL_LIBSQL_PRIMARY_URL").expect("USQL_LIBSQL_PRIMARY_URL");
+        let token = std::env::var("USQL_LIBSQL_AUTH_TOKEN").unwrap_or_default();
+
+        Replica::with_primary(name, ReplicaOptions::new(url, token)).await
+    }
+
+    async fn with_primary(name: &str, replica: ReplicaOptions) -> Replica {
+        let path = temp_path(name);
+        let pool = LibSql::create_pool(Options::default().replica(&path, replica))
+            .await
+            .expect("pool");
+
+        Replica { path, pool }
+    }
+
+    // A plain database file, for replicas to sync from
+    async fn file(name: &str) -> Replica {
+        let path = temp_path(name);
+        let pool = LibSql::create_pool(Options::default().path(&path))
+            .await
+            .expect("pool");
+
+        Replica { path, pool }
+    }
+
+    async fn exec(&self, sql: &str) {
+        let conn = self.pool.get().await.expect("conn");
+        conn.exec_batch(sql).await.expect("exec");
+    }
+
+    async fn count(&self, table: &str) -> i64 {
+        let conn = self.pool.get().await.expect("conn");
+        let mut stmt = conn
+            .prepare(&format!("SELECT COUNT(*) FROM {table}"))
+            .await
+            .expect("prepare");
+        let mut rows = conn.query(&mut stmt, vec![]);
+        let row = next(&mut rows).await.expect("row").expect("row");
+
+        match row.get(ColumnIndex::Index(0)).unwrap().to_owned() {
+            Value::BigInt(n) => n,
+            v => panic!("unexpected value: {v:?}"),
+        }
+    }
+}
+
+impl Drop for Replica {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm", "-info"] {
+            let mut path = self.path.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs a libSQL server, set USQL_LIBSQL_PRIMARY_URL"]
+async fn sync_pulls_writes_from_primary() {
+    let table = format!(
+        "usql_replica_{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    );
+
+    let writer = Replica::open("writer").await;
+    let reader = Replica::open("reader").await;
+
+    // Writes on an embedded replica are forwarded to the primary
+    writer
+        .exec(&format!("CREATE TABLE {table} (id INTEGER PRIMARY KEY)"))
+        .await;
+    reader.pool.sync().await.expect("sync");
+    assert_eq!(reader.count(&table).await, 0);
+
+    writer
+        .exec(&format!("INSERT INTO {table} (id) VALUES (1)"))
+        .await;
+
+    // The reader only sees the row once it has synced
+    assert_eq!(reader.count(&table).await, 0);
+    reader.pool.sync().await.expect("sync");
+    assert_eq!(reader.count(&table).await, 1);
+
+    writer.exec(&format!("DROP TABLE {table}")).await;
+}
+
+#[tokio::test]
+async fn sync_copies_file_primary() {
+    let primary = Replica::file("file-primary").await;
+    primary
+        .exec(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL);
+            CREATE UNIQUE INDEX users_name ON users (name);
+            CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users (id));
+            INSERT INTO users (name) VALUES ('alice'), ('bob');
+            INSERT INTO posts (user_id) VALUES (1);",
+        )
+        .await;
+
+    let replica = Replica::with_primary(
+        "file-replica",
+        ReplicaOptions::new(primary.path.to_string_lossy(), ""),
+    )
+    .await;
+    assert_eq!(replica.count("users").await, 2);
+    assert_eq!(replica.count("posts").await, 1);
+
+    primary
+        .exec(
+            "INSERT INTO users (name) VALUES ('carol');
+            CREATE TABLE tags (name TEXT PRIMARY KEY);
+            INSERT INTO tags (name) VALUES ('rust');",
+        )
+        .await;
+
+    // The replica only sees the new rows once it has synced
+    assert_eq!(replica.count("users").await, 2);
+    replica.pool.sync().await.expect("sync");
+    assert_eq!(replica.count("users").await, 3);
+    assert_eq!(replica.count("tags").await, 1);
+
+    // Indexes and the AUTOINCREMENT counter come along
+    assert_eq!(
+        replica
+            .count("sqlite_schema WHERE type = 'index' AND name = 'users_name'")
+            .await,
+        1
+    );
+    assert_eq!(
+        replica
+            .count("sqlite_sequence WHERE name = 'users' AND seq = 3")
+            .await,
+        1
+    );
+}
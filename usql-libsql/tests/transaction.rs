@@ -0,0 +1,137 @@
+use usql_core::{
+    ColumnIndex, Connection, Connector, Executor, Pool, Row, Transaction, TransactionOptions,
+    util::next,
+};
+use usql_libsql::{LibSql, Options, Pool as LibSqlPool};
+use usql_value::Value;
+
+async fn pool() -> LibSqlPool {
+    let pool = LibSql::create_pool(Options::default()).await.unwrap();
+    pool.get()
+        .await
+        .unwrap()
+        .exec_batch("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)")
+        .await
+        .unwrap();
+    pool
+}
+
+async fn insert<E: Executor<Connector = LibSql>>(exec: &E, name: &str) {
+    exec.exec_batch(&format!("INSERT INTO users (name) VALUES ('{name}')"))
+        .await
+        .unwrap();
+}
+
+async fn count<E: Executor<Connector = LibSql>>(exec: &E) -> i64 {
+    scalar(exec, "SELECT COUNT(*) FROM users").await
+}
+
+async fn scalar<E: Executor<Connector = LibSql>>(exec: &E, sql: &str) -> i64 {
+    let mut stmt = exec.prepare(sql).await.unwrap();
+    let mut rows = exec.query(&mut stmt, vec![]);
+    let row = next(&mut rows).await.unwrap().unwrap();
+
+    match row.get(ColumnIndex::Index(0)).unwrap().to_owned() {
+        Value::BigInt(n) => n,
+        v => panic!("unexpected value: {v:?}"),
+    }
+}
+
+#[tokio::test]
+async fn savepoint_commit_and_rollback() {
+    let mut conn = pool().await.get().await.unwrap();
+
+    let mut trans = conn.begin().await.unwrap();
+    insert(&trans, "outer").await;
+
+    let savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "committed").await;
+    savepoint.commit().await.unwrap();
+    assert_eq!(count(&trans).await, 2);
+
+    let savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "rolled back").await;
+    assert_eq!(count(&savepoint).await, 3);
+    savepoint.rollback().await.unwrap();
+    assert_eq!(count(&trans).await, 2);
+
+    trans.commit().await.unwrap();
+    assert_eq!(count(&conn).await, 2);
+}
+
+#[tokio::test]
+async fn nested_savepoints() {
+    let mut conn = pool().await.get().await.unwrap();
+
+    let mut trans = conn.begin().await.unwrap();
+
+    let mut savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "outer").await;
+    let nested = savepoint.savepoint().await.unwrap();
+    insert(&nested, "nested").await;
+    nested.commit().await.unwrap();
+    assert_eq!(count(&savepoint).await, 2);
+
+    // Rolling back the outer savepoint undoes the committed nested one too
+    savepoint.rollback().await.unwrap();
+    assert_eq!(count(&trans).await, 0);
+
+    let mut savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "outer").await;
+    let nested = savepoint.savepoint().await.unwrap();
+    insert(&nested, "rolled back").await;
+    nested.rollback().await.unwrap();
+    savepoint.commit().await.unwrap();
+
+    trans.commit().await.unwrap();
+    assert_eq!(count(&conn).await, 1);
+}
+
+#[tokio::test]
+async fn dropped_savepoint_rolls_back() {
+    let mut conn = pool().await.get().await.unwrap();
+
+    let mut trans = conn.begin().await.unwrap();
+    insert(&trans, "outer").await;
+
+    let savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "dropped").await;
+    drop(savepoint);
+    // Queued and rolled back before the next command in the transaction
+    assert_eq!(count(&trans).await, 1);
+
+    // Or before the commit, when nothing else runs in between
+    let savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "dropped").await;
+    drop(savepoint);
+
+    trans.commit().await.unwrap();
+    assert_eq!(count(&conn).await, 1);
+}
+
+#[tokio::test]
+async fn read_only_transactions() {
+    let mut conn = pool().await.get().await.unwrap();
+    insert(&conn, "ada").await;
+
+    let read_only = || TransactionOptions::new().read_only(true);
+
+    let trans = conn.begin_with(read_only()).await.unwrap();
+    assert!(
+        trans
+            .exec_batch("INSERT INTO users (name) VALUES ('rejected')")
+            .await
+            .is_err()
+    );
+    assert_eq!(count(&trans).await, 1);
+    trans.commit().await.unwrap();
+
+    // Only the transaction is read only, not the connection
+    insert(&conn, "after commit").await;
+
+    let trans = conn.begin_with(read_only()).await.unwrap();
+    trans.rollback().await.unwrap();
+    insert(&conn, "after rollback").await;
+
+    assert_eq!(count(&conn).await, 3);
+}
@@ -1,8 +1,10 @@
 extern crate proc_macro;
+use std::path::{Path, PathBuf};
+
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
 use quote::{format_ident, quote};
-use syn::{Data, DataEnum, DataStruct, DeriveInput, Field, Generics, parse_macro_input};
+use syn::{Data, DataEnum, DataStruct, DeriveInput, Field, Generics, LitStr, parse_macro_input};
 
 #[proc_macro_derive(FromRow)]
 pub fn derive_from_value(input: TokenStream) -> TokenStream {
@@ -241,3 +243,90 @@ fn derive_enum_from_value(enum_name: Ident, generics: Generics, item: DataEnum)
         .into()
     }
 }
+
+/// Embed a migrations directory, relative to the crate's manifest,
+/// as a `usql_migrate::sql::EmbeddedMigrations` loader
+///
+/// The scripts are included with `include_str!`, so editing one rebuilds the
+/// crate, but cargo doesn't know about migrations or scripts added later.
+/// Have the crate's build script watch the directory to pick those up:
+///
+/// ```ignore
+/// // build.rs
+/// fn main() {
+///     println!("cargo:rerun-if-changed=migrations");
+/// }
+/// ```
+#[proc_macro]
+pub fn embed_migrations(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+
+    match embed_migrations_dir(&path) {
+        Ok(ret) => ret.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+// The same extensions as `usql_migrate::sql::SqlLoader`
+const MIGRATION_EXTS: &[&str] = &["sql", "sqlite", "libsql", "postgres", "mysql"];
+
+fn embed_migrations_dir(path: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let error = |msg: String| syn::Error::new(path.span(), msg);
+
+    let root = std::env::var("CARGO_MANIFEST_DIR").map_err(|err| error(err.to_string()))?;
+    let dir = PathBuf::from(root).join(path.value());
+
+    let mut paths = std::fs::read_dir(&dir)
+        .and_then(|entries| {
+            entries
+                .map(|m| m.map(|m| m.path()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|err| error(format!("{}: {err}", dir.display())))?;
+
+    paths.sort();
+
+    let mut migrations = Vec::with_capacity(paths.len());
+
+    for path in paths {
+        if !path.is_dir() {
+            continue;
+        }
+
+        let up = embed_scripts(&path, "up");
+        if up.is_empty() {
+            continue;
+        }
+
+        let down = embed_scripts(&path, "down");
+
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+
+        migrations.push(quote!(
+            ::usql_migrate::sql::EmbeddedMigration {
+                name: #name,
+                up: &[#(#up),*],
+                down: &[#(#down),*],
+            }
+        ));
+    }
+
+    Ok(quote!(
+        ::usql_migrate::sql::EmbeddedMigrations::new(&[#(#migrations),*])
+    ))
+}
+
+fn embed_scripts(dir: &Path, kind: &str) -> Vec<proc_macro2::TokenStream> {
+    MIGRATION_EXTS
+        .iter()
+        .filter_map(|ext| {
+            let path = dir.join(format!("{kind}.{ext}"));
+            if !path.is_file() {
+                return None;
+            }
+
+            let path = path.to_string_lossy().to_string();
+            Some(quote!((#ext, include_str!(#path))))
+        })
+        .collect()
+}
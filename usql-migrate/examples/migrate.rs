@@ -18,6 +18,8 @@ async fn main() -> Result<(), Box<dyn core::error::Error + Send + Sync>> {
 
     println!("Migrations {}", migrator.has_migrations().await?);
 
+    println!("{:#?}", migrator.plan().await?);
+
     migrator.migrate().await?;
 
     let migrations = migrator.list_migrations().await?;
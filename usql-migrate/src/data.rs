@@ -3,7 +3,7 @@
 //     builder::{
 //         StatementExt,
 //         expr::{ExpressionExt, val},
-//         mutate::{Set, insert},
+//         mutate::{Delete, Set, insert},
 //         schema::{Column, ColumnType, create_table},
 //         select::{FilterQuery, Order, QueryExt, SortQuery, select},
 //     },
@@ -11,16 +11,18 @@
 //     value::{JsonValue, Type, chrono::NaiveDateTime},
 // };
 
+use std::collections::BTreeMap;
+
 use chrono::NaiveDateTime;
 use usql_builder::{
     StatementExt,
     expr::{ExpressionExt, val},
-    mutate::{Set, insert},
+    mutate::{Delete, Set, insert},
     schema::{Column, ColumnType, create_table},
     select::{FilterQuery, Order, QueryExt, SortQuery, select},
 };
-use usql_core::{ColumnIndex, Connector, DatabaseInfo, Executor, Row, util::next};
-use usql_value::{JsonValue, Type};
+use usql_core::{ColumnIndex, Connector, DatabaseInfo, Executor, Row, System, util::next};
+use usql_value::{JsonValue, Type, Value, ValueCow};
 
 use crate::error::Error;
 
@@ -28,10 +30,57 @@ use crate::error::Error;
 pub struct Entry {
     pub name: String,
     pub date: NaiveDateTime,
-    #[allow(unused)]
     pub meta: Option<JsonValue>,
 }
 
+impl Entry {
+    /// The checksum of the migration when it was applied
+    pub fn checksum(&self) -> Option<&str> {
+        match &self.meta {
+            Some(JsonValue::Object(meta)) => match meta.get("checksum") {
+                Some(JsonValue::String(checksum)) => Some(checksum),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Whether the migrations table exists, without creating it
+pub async fn has_table<E>(executor: &E, table: &str) -> Result<bool, Error<E::Connector>>
+where
+    E: Executor,
+    <E::Connector as Connector>::Error: core::error::Error + Send + Sync + 'static,
+{
+    let sql = match executor.db_info().variant() {
+        System::Sqlite | System::LibSql => {
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?"
+        }
+        System::Postgres => {
+            "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = current_schema() AND table_name = $1"
+        }
+        System::Mysql => {
+            "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = DATABASE() AND table_name = ?"
+        }
+    };
+
+    let mut stmt = executor.prepare(sql).await.map_err(Error::Connector)?;
+
+    let mut stream = executor.query(&mut stmt, vec![ValueCow::Owned(Value::from(table))]);
+
+    let Some(row) = next(&mut stream).await else {
+        return Ok(false);
+    };
+
+    let count = row
+        .map_err(Error::Connector)?
+        .get_typed(ColumnIndex::Index(0), Type::BigInt)
+        .map_err(Error::Connector)?
+        .to_owned();
+
+    Ok(matches!(count, Value::BigInt(n) if n > 0))
+}
+
 pub async fn ensure_table<E>(executor: &E, table: &str) -> Result<(), Error<E::Connector>>
 where
     E: Executor,
@@ -39,7 +88,8 @@ where
 {
     let sql = create_table(table)
         .column(
-            Column::new("name", ColumnType::Text)
+            // MySQL can't key a TEXT column
+            Column::new("name", ColumnType::VarChar(255))
                 .required(true)
                 .primary_key(),
         )
@@ -47,9 +97,12 @@ where
         .column(Column::new("meta", ColumnType::Json))
         .to_sql(executor.db_info().variant())?;
 
-    let mut stmt = executor.prepare(&sql.sql).await.unwrap();
+    let mut stmt = executor.prepare(&sql.sql).await.map_err(Error::Connector)?;
 
-    executor.exec(&mut stmt, sql.bindings).await.unwrap();
+    executor
+        .exec(&mut stmt, sql.bindings)
+        .await
+        .map_err(Error::Connector)?;
 
     Ok(())
 }
@@ -64,26 +117,26 @@ where
         .into_stmt()
         .to_sql(executor.db_info().variant())?;
 
-    let mut stmt = executor.prepare(&sql.sql).await.unwrap();
+    let mut stmt = executor.prepare(&sql.sql).await.map_err(Error::Connector)?;
 
     let mut stream = executor.query(&mut stmt, sql.bindings);
 
     let mut output = Vec::new();
 
     while let Some(row) = next(&mut stream).await {
-        let row = row.unwrap();
+        let row = row.map_err(Error::Connector)?;
 
         let name = row
             .get_typed(ColumnIndex::Index(0), Type::Text)
-            .unwrap()
+            .map_err(Error::Connector)?
             .to_owned();
         let date = row
             .get_typed(ColumnIndex::Index(1), Type::DateTime)
-            .unwrap()
+            .map_err(Error::Connector)?
             .to_owned();
         let meta = row
             .get_typed(ColumnIndex::Index(2), Type::Json)
-            .unwrap()
+            .map_err(Error::Connector)?
             .to_owned();
 
         let entry = Entry {
@@ -112,7 +165,7 @@ where
         .into_stmt()
         .to_sql(executor.db_info().variant())?;
 
-    let mut stmt = executor.prepare(&sql.sql).await.unwrap();
+    let mut stmt = executor.prepare(&sql.sql).await.map_err(Error::Connector)?;
 
     let mut stream = executor.query(&mut stmt, sql.bindings);
 
@@ -120,19 +173,19 @@ where
         return Ok(None);
     };
 
-    let row = next.unwrap();
+    let row = next.map_err(Error::Connector)?;
 
     let name = row
         .get_typed(ColumnIndex::Index(0), Type::Text)
-        .unwrap()
+        .map_err(Error::Connector)?
         .to_owned();
     let date = row
         .get_typed(ColumnIndex::Index(1), Type::DateTime)
-        .unwrap()
+        .map_err(Error::Connector)?
         .to_owned();
     let meta = row
         .get_typed(ColumnIndex::Index(2), Type::Json)
-        .unwrap()
+        .map_err(Error::Connector)?
         .to_owned();
 
     let entry = Entry {
@@ -149,19 +202,52 @@ pub async fn insert_migration<E>(
     table: &str,
     name: &str,
     date: NaiveDateTime,
+    checksum: Option<&str>,
 ) -> Result<(), Error<E::Connector>>
 where
     E: Executor,
     <E::Connector as Connector>::Error: core::error::Error + Send + Sync + 'static,
 {
-    let sql = insert(table)
+    let mut stmt = insert(table)
         .with("name", val(name))
-        .with("date", val(date))
+        .with("date", val(date));
+
+    if let Some(checksum) = checksum {
+        let meta = BTreeMap::from([("checksum".to_string(), JsonValue::from(checksum))]);
+        stmt.set("meta", val(JsonValue::from(meta)));
+    }
+
+    let sql = stmt.to_sql(executor.db_info().variant())?;
+
+    let mut stmt = executor.prepare(&sql.sql).await.map_err(Error::Connector)?;
+
+    executor
+        .exec(&mut stmt, sql.bindings)
+        .await
+        .map_err(Error::Connector)?;
+
+    Ok(())
+}
+
+pub async fn delete_migration<E>(
+    executor: &E,
+    table: &str,
+    name: &str,
+) -> Result<(), Error<E::Connector>>
+where
+    E: Executor,
+    <E::Connector as Connector>::Error: core::error::Error + Send + Sync + 'static,
+{
+    let sql = Delete::new(table)
+        .filter("name".eql(val(name)))
         .to_sql(executor.db_info().variant())?;
 
-    let mut stmt = executor.prepare(&sql.sql).await.unwrap();
+    let mut stmt = executor.prepare(&sql.sql).await.map_err(Error::Connector)?;
 
-    executor.exec(&mut stmt, sql.bindings).await.unwrap();
+    executor
+        .exec(&mut stmt, sql.bindings)
+        .await
+        .map_err(Error::Connector)?;
 
     Ok(())
 }
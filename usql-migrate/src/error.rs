@@ -1,4 +1,4 @@
-use usql_core::Connector;
+use usql_core::{Connector, System};
 
 #[derive(thiserror::Error)]
 pub enum Error<T: Connector> {
@@ -8,6 +8,16 @@ pub enum Error<T: Connector> {
     Query(#[from] usql_builder::Error),
     #[error("Load: {0}")]
     Load(Box<dyn core::error::Error + Send + Sync>),
+    #[error("Migration '{0}' is applied but could not be found")]
+    Missing(String),
+    #[error("Migration '{0}' is pending but sorts before an applied migration")]
+    OutOfOrder(String),
+    #[error("Migration '{0}' has no down script and can't be rolled back")]
+    Irreversible(String),
+    #[error("Migration has no script for {0:?}")]
+    NoScript(System),
+    #[error("Dry runs need transactional DDL, which {0:?} doesn't have")]
+    DryRunUnsupported(System),
 }
 
 impl<T> core::fmt::Debug for Error<T>
@@ -20,6 +30,13 @@ where
             Error::Connector(err) => f.debug_tuple("Connector").field(err).finish(),
             Error::Query(err) => f.debug_tuple("Query").field(err).finish(),
             Error::Load(err) => f.debug_tuple("Load").field(err).finish(),
+            Error::Missing(name) => f.debug_tuple("Missing").field(name).finish(),
+            Error::OutOfOrder(name) => f.debug_tuple("OutOfOrder").field(name).finish(),
+            Error::Irreversible(name) => f.debug_tuple("Irreversible").field(name).finish(),
+            Error::NoScript(system) => f.debug_tuple("NoScript").field(system).finish(),
+            Error::DryRunUnsupported(system) => {
+                f.debug_tuple("DryRunUnsupported").field(system).finish()
+            }
         }
     }
 }
@@ -1,4 +1,4 @@
-use usql_core::{Connection, Connector, Executor, QueryStream};
+use usql_core::{Connection, Connector, Executor, QueryResult, QueryStream};
 use usql_value::ValueCow;
 
 pub struct Exec<'a, B: Connector>
@@ -54,7 +54,8 @@ where
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: Vec<ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
         async move { self.conn.exec(stmt, params).await }
     }
 
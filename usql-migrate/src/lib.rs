@@ -12,6 +12,6 @@ pub use self::{
     error::Error,
     exec::Exec,
     loader::MigrationLoader,
-    migration::{Migration, MigrationInfo, Runner},
+    migration::{Drift, FnRunner, Migration, MigrationInfo, PlannedMigration, Runner},
     migrator::Migrator,
 };
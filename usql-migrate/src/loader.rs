@@ -9,6 +9,12 @@ pub trait MigrationLoader<B: Connector> {
     type Migration: Runner<B>;
     type Error;
 
+    /// Names of migrations the loader provides without a file in the migrations directory,
+    /// they're passed to `load` as `path.join(name)` and sorted together with the files
+    fn names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
     fn can_load<'a>(&'a self, path: &'a Path) -> impl Future<Output = bool> + Send + 'a;
 
     fn load<'a>(
@@ -30,6 +36,10 @@ macro_rules! loaders {
             type Migration = Box<dyn DynamicRunner<B>>;
             type Error = Error<B>;
 
+            fn names(&self) -> Vec<String> {
+                self.0.names()
+            }
+
             fn can_load<'a>(&'a self, path: &'a Path) -> impl Future<Output = bool> + Send + 'a {
                 async move { self.0.can_load(path).await }
             }
@@ -66,6 +76,15 @@ macro_rules! loaders {
             type Migration = Box<dyn DynamicRunner<B>>;
             type Error = Error<B>;
 
+            fn names(&self) -> Vec<String> {
+                let ($first, $($rest),+) = self;
+                let mut names = $first.names();
+                $(
+                    names.extend($rest.names());
+                )+
+                names
+            }
+
             fn can_load<'a>(&'a self, path: &'a Path) -> impl Future<Output = bool> + Send + 'a {
                 async move {
                     let ($first, $($rest),+) = self;
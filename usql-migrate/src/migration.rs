@@ -1,7 +1,8 @@
 use crate::{error::Error, exec::Exec};
 use chrono::NaiveDateTime;
 use futures::future::BoxFuture;
-use usql_core::{Connection, Connector};
+use std::sync::Arc;
+use usql_core::{Connection, Connector, System};
 
 #[derive(Debug)]
 pub struct Migration<T> {
@@ -15,6 +16,24 @@ pub struct MigrationInfo<T> {
     pub runner: T,
 }
 
+/// A pending migration and the SQL it would run, when it's known up front
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PlannedMigration {
+    pub name: String,
+    pub script: Option<String>,
+}
+
+/// A difference between the applied migrations and the loaded ones
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Drift {
+    /// The migration was edited after it was applied
+    Changed(String),
+    /// The migration is applied but could not be found
+    Missing(String),
+    /// The migration is pending but sorts before an applied migration
+    OutOfOrder(String),
+}
+
 pub trait Runner<B: Connector> {
     type Error;
 
@@ -27,12 +46,33 @@ pub trait Runner<B: Connector> {
         &'a self,
         executor: &'a Exec<'_, B>,
     ) -> impl Future<Output = Result<(), Self::Error>> + Send + 'a;
+
+    /// A hash of the migration's content, stored when it's applied so later edits can be detected
+    fn checksum(&self) -> Option<String> {
+        None
+    }
+
+    /// The script `up` would run on `system`, if it's known without running it
+    fn script(&self, _system: System) -> Option<&str> {
+        None
+    }
+
+    /// Whether `down` can undo the migration on `system`
+    fn reversible(&self, _system: System) -> bool {
+        true
+    }
 }
 
 pub trait DynamicRunner<B: Connector>: Send + Sync {
     fn up<'a>(&'a self, conn: &'a Exec<'_, B>) -> BoxFuture<'a, Result<(), Error<B>>>;
 
     fn down<'a>(&'a self, conn: &'a Exec<'_, B>) -> BoxFuture<'a, Result<(), Error<B>>>;
+
+    fn checksum(&self) -> Option<String>;
+
+    fn script(&self, system: System) -> Option<&str>;
+
+    fn reversible(&self, system: System) -> bool;
 }
 
 impl<B> Runner<B> for Box<dyn DynamicRunner<B>>
@@ -55,6 +95,18 @@ where
     ) -> impl Future<Output = Result<(), Self::Error>> + Send + 'a {
         async move { (**self).down(executor).await }
     }
+
+    fn checksum(&self) -> Option<String> {
+        (**self).checksum()
+    }
+
+    fn script(&self, system: System) -> Option<&str> {
+        (**self).script(system)
+    }
+
+    fn reversible(&self, system: System) -> bool {
+        (**self).reversible(system)
+    }
 }
 
 pub fn runner_box<M, B>(migration: M) -> Box<dyn DynamicRunner<B>>
@@ -83,4 +135,99 @@ where
     fn down<'a>(&'a self, conn: &'a Exec<'_, B>) -> BoxFuture<'a, Result<(), Error<B>>> {
         Box::pin(async move { self.0.down(conn).await.map_err(Error::load) })
     }
+
+    fn checksum(&self) -> Option<String> {
+        self.0.checksum()
+    }
+
+    fn script(&self, system: System) -> Option<&str> {
+        self.0.script(system)
+    }
+
+    fn reversible(&self, system: System) -> bool {
+        self.0.reversible(system)
+    }
+}
+
+type MigrationFn<B> =
+    dyn for<'a, 'b> Fn(&'a Exec<'b, B>) -> BoxFuture<'a, Result<(), Error<B>>> + Send + Sync;
+
+/// A migration written in Rust, added with `Migrator::register`.
+///
+/// Without a down step it can't be rolled back
+pub struct FnRunner<B: Connector> {
+    up: Arc<MigrationFn<B>>,
+    down: Option<Arc<MigrationFn<B>>>,
+}
+
+impl<B: Connector> FnRunner<B> {
+    pub fn new<F>(up: F) -> FnRunner<B>
+    where
+        F: for<'a, 'b> Fn(&'a Exec<'b, B>) -> BoxFuture<'a, Result<(), Error<B>>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        FnRunner {
+            up: Arc::new(up),
+            down: None,
+        }
+    }
+
+    pub fn down<F>(mut self, down: F) -> Self
+    where
+        F: for<'a, 'b> Fn(&'a Exec<'b, B>) -> BoxFuture<'a, Result<(), Error<B>>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.down = Some(Arc::new(down));
+        self
+    }
+}
+
+impl<B: Connector> Clone for FnRunner<B> {
+    fn clone(&self) -> Self {
+        FnRunner {
+            up: self.up.clone(),
+            down: self.down.clone(),
+        }
+    }
+}
+
+impl<B> DynamicRunner<B> for FnRunner<B>
+where
+    B: Connector,
+{
+    fn up<'a>(&'a self, conn: &'a Exec<'_, B>) -> BoxFuture<'a, Result<(), Error<B>>> {
+        (self.up)(conn)
+    }
+
+    fn down<'a>(&'a self, conn: &'a Exec<'_, B>) -> BoxFuture<'a, Result<(), Error<B>>> {
+        match &self.down {
+            Some(down) => down(conn),
+            None => Box::pin(async move { Err(Error::load("Migration has no down step")) }),
+        }
+    }
+
+    fn checksum(&self) -> Option<String> {
+        None
+    }
+
+    fn script(&self, _system: System) -> Option<&str> {
+        None
+    }
+
+    fn reversible(&self, _system: System) -> bool {
+        self.down.is_some()
+    }
+}
+
+impl<B> From<FnRunner<B>> for Box<dyn DynamicRunner<B>>
+where
+    B: Connector + 'static,
+{
+    fn from(value: FnRunner<B>) -> Self {
+        Box::new(value)
+    }
 }
@@ -1,4 +1,4 @@
-use futures::TryStreamExt;
+use futures::{TryStreamExt, future::BoxFuture};
 use std::{
     collections::HashSet,
     path::{Path, PathBuf},
@@ -11,24 +11,30 @@ use std::{
 // };
 
 use chrono::Utc;
-use usql_core::{Connection, Connector, Executor, Pool, Transaction};
+use usql_core::{Connection, Connector, DatabaseInfo, Executor, Pool, System, Transaction};
 
 use crate::{
-    data::{Entry, ensure_table, get_entry, insert_migration, list_entries},
+    data::{
+        Entry, delete_migration, ensure_table, get_entry, has_table, insert_migration, list_entries,
+    },
     error::Error,
     exec::Exec,
     loader::MigrationLoader,
-    migration::{Migration, MigrationInfo, Runner},
+    migration::{Drift, FnRunner, Migration, MigrationInfo, PlannedMigration, Runner},
 };
 
+type Registered<M> = Box<dyn Fn() -> M + Send + Sync>;
+
 pub struct Migrator<B, T>
 where
     B: Connector,
+    T: MigrationLoader<B>,
 {
     pool: B::Pool,
     loader: T,
     path: PathBuf,
     table_name: String,
+    registered: Vec<(String, Registered<T::Migration>)>,
 }
 
 impl<B, T> Migrator<B, T>
@@ -51,9 +57,51 @@ where
             loader,
             path,
             table_name,
+            registered: Vec::new(),
         }
     }
 
+    /// Add a migration written in Rust, it's ordered by name together with the loaded ones.
+    /// It has no down step, so it can't be rolled back.
+    ///
+    /// The loader has to produce boxed runners, which tuples of loaders do,
+    /// so wrap a single loader like `Migrator::new(pool, (SqlLoader,), path, table)`
+    pub fn register<F>(self, name: impl Into<String>, up: F) -> Self
+    where
+        F: for<'a, 'b> Fn(&'a Exec<'b, B>) -> BoxFuture<'a, Result<(), Error<B>>>
+            + Send
+            + Sync
+            + 'static,
+        T::Migration: From<FnRunner<B>>,
+    {
+        self.register_runner(name, FnRunner::new(up))
+    }
+
+    /// Add a migration written in Rust with a down step, see `register`
+    pub fn register_reversible<U, D>(self, name: impl Into<String>, up: U, down: D) -> Self
+    where
+        U: for<'a, 'b> Fn(&'a Exec<'b, B>) -> BoxFuture<'a, Result<(), Error<B>>>
+            + Send
+            + Sync
+            + 'static,
+        D: for<'a, 'b> Fn(&'a Exec<'b, B>) -> BoxFuture<'a, Result<(), Error<B>>>
+            + Send
+            + Sync
+            + 'static,
+        T::Migration: From<FnRunner<B>>,
+    {
+        self.register_runner(name, FnRunner::new(up).down(down))
+    }
+
+    fn register_runner(mut self, name: impl Into<String>, runner: FnRunner<B>) -> Self
+    where
+        T::Migration: From<FnRunner<B>>,
+    {
+        let factory: Registered<T::Migration> = Box::new(move || runner.clone().into());
+        self.registered.push((name.into(), factory));
+        self
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -113,6 +161,152 @@ where
 
         Ok(ret)
     }
+
+    /// Compare the applied migrations with the loaded ones, reporting
+    /// edited, missing and out of order migrations
+    pub async fn verify(&self) -> Result<Vec<Drift>, Error<B>> {
+        let migrations = self.load_migrations().await?;
+        let conn = self.pool.get().await.map_err(Error::Connector)?;
+        let entries = self.load_entries(&conn).await?;
+
+        let mut drift = Vec::new();
+        let mut last_applied = 0;
+
+        for entry in &entries {
+            let Some(idx) = migrations.iter().position(|m| m.name == entry.name) else {
+                drift.push(Drift::Missing(entry.name.clone()));
+                continue;
+            };
+
+            let checksum = migrations[idx].runner.checksum();
+            if entry
+                .checksum()
+                .zip(checksum.as_deref())
+                .is_some_and(|(applied, checksum)| applied != checksum)
+            {
+                drift.push(Drift::Changed(entry.name.clone()));
+            }
+
+            last_applied = last_applied.max(idx + 1);
+        }
+
+        for migration in &migrations[..last_applied] {
+            if !entries.iter().any(|e| e.name == migration.name) {
+                drift.push(Drift::OutOfOrder(migration.name.clone()));
+            }
+        }
+
+        Ok(drift)
+    }
+
+    /// The pending migrations and the script each would run on this database, nothing is executed.
+    ///
+    /// Doesn't create the migrations table either, without it every migration is pending
+    pub async fn plan(&self) -> Result<Vec<PlannedMigration>, Error<B>> {
+        let migrations = self.load_migrations().await?;
+        let conn = self.pool.get().await.map_err(Error::Connector)?;
+
+        let entries = match has_table(&conn, &self.table_name).await? {
+            true => list_entries(&conn, &self.table_name).await?,
+            false => Vec::new(),
+        };
+        let pending = Self::first_pending(&entries, &migrations)?;
+        let variant = conn.db_info().variant();
+
+        let plan = migrations[pending..]
+            .iter()
+            .map(|migration| PlannedMigration {
+                name: migration.name.clone(),
+                script: migration.runner.script(variant).map(str::to_string),
+            })
+            .collect();
+
+        Ok(plan)
+    }
+
+    /// Run the pending migrations in a single transaction which is always rolled back,
+    /// returning the plan when all of them succeed.
+    ///
+    /// MySQL commits implicitly on schema changes, so it returns an error instead
+    pub async fn dry_run(&self) -> Result<Vec<PlannedMigration>, Error<B>> {
+        let migrations = self.load_migrations().await?;
+        let mut conn = self.pool.get().await.map_err(Error::Connector)?;
+
+        let variant = conn.db_info().variant();
+        if variant == System::Mysql {
+            return Err(Error::DryRunUnsupported(variant));
+        }
+
+        let trans = conn.begin().await.map_err(Error::Connector)?;
+
+        let entries = self.load_entries(&trans).await?;
+        let pending = Self::first_pending(&entries, &migrations)?;
+
+        let exec = Exec::new(trans);
+
+        let mut plan = Vec::with_capacity(migrations.len() - pending);
+        for migration in &migrations[pending..] {
+            migration.runner.up(&exec).await.map_err(Error::load)?;
+
+            insert_migration(
+                &exec,
+                &self.table_name,
+                &migration.name,
+                Utc::now().naive_utc(),
+                migration.runner.checksum().as_deref(),
+            )
+            .await?;
+
+            plan.push(PlannedMigration {
+                name: migration.name.clone(),
+                script: migration.runner.script(variant).map(str::to_string),
+            });
+        }
+
+        exec.conn.rollback().await.map_err(Error::Connector)?;
+
+        Ok(plan)
+    }
+
+    /// Roll back the last applied migration
+    pub async fn rollback(&self) -> Result<bool, Error<B>> {
+        let migrations = self.load_migrations().await?;
+        let mut conn = self.pool.get().await.map_err(Error::Connector)?;
+        let ret = self.rollback_one(&mut conn, &migrations, 0).await?;
+        Ok(ret)
+    }
+
+    /// Roll back every migration applied after `name`, `name` itself stays applied
+    pub async fn rollback_to(&self, name: &str) -> Result<bool, Error<B>> {
+        let migrations = self.load_migrations().await?;
+        let mut conn = self.pool.get().await.map_err(Error::Connector)?;
+
+        let entries = self.load_entries(&conn).await?;
+        let Some(idx) = entries.iter().position(|m| m.name == name) else {
+            return Err(Error::load(format!("Migration '{}' is not applied", name)));
+        };
+
+        let mut ret = false;
+        loop {
+            if !self.rollback_one(&mut conn, &migrations, idx + 1).await? {
+                break;
+            }
+            ret = true;
+        }
+
+        Ok(ret)
+    }
+
+    /// Roll back the last applied migration and apply it again
+    pub async fn redo(&self) -> Result<bool, Error<B>> {
+        let migrations = self.load_migrations().await?;
+        let mut conn = self.pool.get().await.map_err(Error::Connector)?;
+        if !self.rollback_one(&mut conn, &migrations, 0).await? {
+            return Ok(false);
+        }
+        let ret = self.migration_one(&mut conn, &migrations).await?;
+        Ok(ret)
+    }
 }
 
 impl<B, T> Migrator<B, T>
@@ -139,19 +333,9 @@ where
 
         let entries = self.load_entries(&trans).await?;
 
-        if entries.len() > migrations.len() {
-            panic!("Invalid state")
-        } else if entries.len() == migrations.len() {
+        let Some(migration) = migrations.get(Self::first_pending(&entries, migrations)?) else {
             return Ok(false);
-        }
-
-        for (entry, migration) in entries.iter().zip(migrations.iter().take(entries.len())) {
-            if entry.name != migration.name {
-                panic!("invalid state")
-            }
-        }
-
-        let migration = &migrations[entries.len()];
+        };
 
         let exec = Exec::new(trans);
 
@@ -162,6 +346,7 @@ where
             &self.table_name,
             &migration.name,
             Utc::now().naive_utc(),
+            migration.runner.checksum().as_deref(),
         )
         .await?;
 
@@ -170,16 +355,69 @@ where
         Ok(true)
     }
 
+    /// Roll back the last applied migration, unless only `keep` or fewer are applied
+    async fn rollback_one(
+        &self,
+        conn: &mut B::Connection,
+        migrations: &[MigrationInfo<T::Migration>],
+        keep: usize,
+    ) -> Result<bool, Error<B>> {
+        let variant = conn.db_info().variant();
+        let trans = conn.begin().await.map_err(Error::Connector)?;
+
+        let entries = self.load_entries(&trans).await?;
+
+        if entries.len() <= keep {
+            return Ok(false);
+        }
+
+        let Some(entry) = entries.last() else {
+            return Ok(false);
+        };
+
+        let Some(migration) = migrations.iter().find(|m| m.name == entry.name) else {
+            return Err(Error::Missing(entry.name.clone()));
+        };
+
+        if !migration.runner.reversible(variant) {
+            return Err(Error::Irreversible(entry.name.clone()));
+        }
+
+        let exec = Exec::new(trans);
+
+        migration.runner.down(&exec).await.map_err(Error::load)?;
+
+        delete_migration(&exec, &self.table_name, &entry.name).await?;
+
+        exec.conn.commit().await.map_err(Error::Connector)?;
+
+        Ok(true)
+    }
+
     async fn load_migrations(&self) -> Result<Vec<MigrationInfo<T::Migration>>, Error<B>> {
-        let readdir = tokio::fs::read_dir(&self.path).await.map_err(Error::load)?;
+        let names = self.loader.names();
+
+        // Embedded migrations don't need the directory to exist
+        let mut readdir = if names.is_empty()
+            || tokio::fs::try_exists(&self.path)
+                .await
+                .map_err(Error::load)?
+        {
+            let readdir = tokio::fs::read_dir(&self.path).await.map_err(Error::load)?;
+
+            tokio_stream::wrappers::ReadDirStream::new(readdir)
+                .map_ok(|m| m.path())
+                .try_collect::<Vec<_>>()
+                .await
+                .map_err(Error::load)?
+        } else {
+            Vec::new()
+        };
 
-        let mut readdir = tokio_stream::wrappers::ReadDirStream::new(readdir)
-            .map_ok(|m| m.path())
-            .try_collect::<Vec<_>>()
-            .await
-            .map_err(Error::load)?;
+        readdir.extend(names.into_iter().map(|name| self.path.join(name)));
 
         readdir.sort();
+        readdir.dedup();
 
         let mut migrations = Vec::with_capacity(readdir.len());
 
@@ -203,6 +441,19 @@ where
             migrations.push(MigrationInfo { name, runner });
         }
 
+        for (name, runner) in &self.registered {
+            if !seen.insert(name.clone()) {
+                return Err(Error::load(format!("Migration '{}' already found", name)));
+            }
+
+            migrations.push(MigrationInfo {
+                name: name.clone(),
+                runner: runner(),
+            });
+        }
+
+        migrations.sort_by(|a, b| a.name.cmp(&b.name));
+
         Ok(migrations)
     }
 
@@ -215,4 +466,22 @@ where
         ensure_table(conn, &self.table_name).await?;
         list_entries(conn, &self.table_name).await
     }
+
+    /// The index of the first pending migration, the applied ones have to match the loaded ones in order
+    fn first_pending(
+        entries: &[Entry],
+        migrations: &[MigrationInfo<T::Migration>],
+    ) -> Result<usize, Error<B>> {
+        for (idx, entry) in entries.iter().enumerate() {
+            match migrations.get(idx) {
+                Some(migration) if migration.name == entry.name => {}
+                Some(migration) if migrations.iter().any(|m| m.name == entry.name) => {
+                    return Err(Error::OutOfOrder(migration.name.clone()));
+                }
+                _ => return Err(Error::Missing(entry.name.clone())),
+            }
+        }
+
+        Ok(entries.len())
+    }
 }
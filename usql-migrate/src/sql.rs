@@ -1,14 +1,18 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::Path};
 
+use sha2::{Digest, Sha256};
 use tokio::fs;
 use usql_core::{Connection, Connector, DatabaseInfo, Executor, System};
 
-use crate::{Exec, MigrationLoader, Runner};
+use crate::{Error, Exec, MigrationLoader, Runner};
+
+#[cfg(feature = "embed")]
+pub use usql_macros::embed_migrations;
 
 #[derive(Default)]
 pub struct SqlLoader;
 
-const EXTS: &'static [&'static str] = &["sql", "sqlite", "libsql", "postgres"];
+const EXTS: &'static [&'static str] = &["sql", "sqlite", "libsql", "postgres", "mysql"];
 
 impl<B> MigrationLoader<B> for SqlLoader
 where
@@ -49,38 +53,12 @@ where
 
                 if fs::metadata(&up_path).await.is_ok() {
                     let content = fs::read_to_string(&up_path).await.unwrap();
-                    match *ext {
-                        "postgres" => {
-                            up.scripts.insert(System::Postgres, content);
-                        }
-                        "sqlite" => {
-                            up.scripts.insert(System::Sqlite, content);
-                        }
-                        "libsql" => {
-                            up.scripts.insert(System::LibSql, content);
-                        }
-                        _ => {
-                            up.default = Some(content);
-                        }
-                    };
+                    up.insert(ext, content);
                 }
 
                 if fs::metadata(&down_path).await.is_ok() {
                     let content = fs::read_to_string(&down_path).await.unwrap();
-                    match *ext {
-                        "postgres" => {
-                            down.scripts.insert(System::Postgres, content);
-                        }
-                        "sqlite" => {
-                            down.scripts.insert(System::Sqlite, content);
-                        }
-                        "libsql" => {
-                            down.scripts.insert(System::LibSql, content);
-                        }
-                        _ => {
-                            down.default = Some(content);
-                        }
-                    };
+                    down.insert(ext, content);
                 }
             }
 
@@ -89,6 +67,77 @@ where
     }
 }
 
+/// Migrations compiled into the binary, created with `embed_migrations!`.
+/// See the macro for rebuilding when migrations are added.
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedMigrations {
+    migrations: &'static [EmbeddedMigration],
+}
+
+/// A migration directory embedded by `embed_migrations!`,
+/// the scripts are keyed by their extension like `up.postgres`
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedMigration {
+    pub name: &'static str,
+    pub up: &'static [(&'static str, &'static str)],
+    pub down: &'static [(&'static str, &'static str)],
+}
+
+impl EmbeddedMigrations {
+    pub const fn new(migrations: &'static [EmbeddedMigration]) -> EmbeddedMigrations {
+        EmbeddedMigrations { migrations }
+    }
+
+    fn find(&self, path: &Path) -> Option<&EmbeddedMigration> {
+        let name = path.file_stem()?;
+        self.migrations.iter().find(|m| name == m.name)
+    }
+}
+
+impl<B> MigrationLoader<B> for EmbeddedMigrations
+where
+    B: Connector,
+    for<'a> <B::Connection as Connection>::Transaction<'a>: Send + Sync,
+{
+    type Migration = SqlRunner;
+
+    type Error = Error<B>;
+
+    fn names(&self) -> Vec<String> {
+        self.migrations.iter().map(|m| m.name.to_string()).collect()
+    }
+
+    fn can_load<'a>(&'a self, path: &'a Path) -> impl Future<Output = bool> + Send + 'a {
+        async move { self.find(path).is_some() }
+    }
+
+    fn load<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> impl Future<Output = Result<Self::Migration, Self::Error>> + Send + 'a {
+        async move {
+            let Some(migration) = self.find(path) else {
+                return Err(Error::load(format!(
+                    "Migration '{}' is not embedded",
+                    path.display()
+                )));
+            };
+
+            let mut up = Script::default();
+            for (ext, content) in migration.up {
+                up.insert(ext, content.to_string());
+            }
+
+            let mut down = Script::default();
+            for (ext, content) in migration.down {
+                down.insert(ext, content.to_string());
+            }
+
+            Ok(SqlRunner { up, down })
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 struct Script {
     scripts: HashMap<System, String>,
@@ -96,22 +145,71 @@ struct Script {
 }
 
 impl Script {
-    async fn run<B: Connector>(&self, exec: &Exec<'_, B>) -> Result<(), B::Error>
+    fn insert(&mut self, ext: &str, content: String) {
+        match ext {
+            "postgres" => {
+                self.scripts.insert(System::Postgres, content);
+            }
+            "sqlite" => {
+                self.scripts.insert(System::Sqlite, content);
+            }
+            "libsql" => {
+                self.scripts.insert(System::LibSql, content);
+            }
+            "mysql" => {
+                self.scripts.insert(System::Mysql, content);
+            }
+            _ => {
+                self.default = Some(content);
+            }
+        };
+    }
+
+    async fn run<B: Connector>(&self, exec: &Exec<'_, B>) -> Result<(), Error<B>>
     where
         for<'a> <B::Connection as Connection>::Transaction<'a>: Send + Sync,
     {
-        let script = if let Some(script) = &self.scripts.get(&exec.conn.db_info().variant()) {
-            script
-        } else {
-            match &self.default {
-                Some(ret) => ret,
-                None => {
-                    panic!("no default script")
-                }
-            }
+        let system = exec.conn.db_info().variant();
+        let Some(script) = self.get(system) else {
+            return Err(Error::NoScript(system));
         };
 
-        exec.exec_batch(script).await
+        exec.exec_batch(script).await.map_err(Error::Connector)
+    }
+
+    fn get(&self, system: System) -> Option<&str> {
+        self.scripts
+            .get(&system)
+            .or(self.default.as_ref())
+            .map(|script| script.as_str())
+    }
+
+    // Every script is tagged with the extension it was loaded from,
+    // so the checksum doesn't depend on how `System` is formatted
+    fn hash(&self, hasher: &mut Sha256) {
+        let mut scripts = self
+            .scripts
+            .iter()
+            .map(|(system, script)| (ext(*system), script))
+            .chain(self.default.iter().map(|script| ("sql", script)))
+            .collect::<Vec<_>>();
+        scripts.sort_by_key(|(ext, _)| *ext);
+
+        for (ext, script) in scripts {
+            hasher.update(ext);
+            hasher.update("\0");
+            hasher.update(script);
+            hasher.update("\0");
+        }
+    }
+}
+
+fn ext(system: System) -> &'static str {
+    match system {
+        System::Sqlite => "sqlite",
+        System::LibSql => "libsql",
+        System::Postgres => "postgres",
+        System::Mysql => "mysql",
     }
 }
 
@@ -125,7 +223,7 @@ impl<B: Connector> Runner<B> for SqlRunner
 where
     for<'a> <B::Connection as Connection>::Transaction<'a>: Send + Sync,
 {
-    type Error = B::Error;
+    type Error = Error<B>;
 
     fn up<'a>(
         &'a self,
@@ -146,4 +244,23 @@ where
             Ok(())
         }
     }
+
+    fn checksum(&self) -> Option<String> {
+        let mut hasher = Sha256::new();
+        self.up.hash(&mut hasher);
+        let checksum = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        Some(checksum)
+    }
+
+    fn script(&self, system: System) -> Option<&str> {
+        self.up.get(system)
+    }
+
+    fn reversible(&self, system: System) -> bool {
+        self.down.get(system).is_some()
+    }
 }
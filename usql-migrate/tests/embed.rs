@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use futures::TryStreamExt;
+use usql_core::{ColumnIndex, Connector, Executor, Pool, Row, System};
+use usql_migrate::{
+    MigrationLoader, Migrator, Runner,
+    sql::{EmbeddedMigrations, embed_migrations},
+};
+use usql_sqlite::{Sqlite, SqliteOptions};
+use usql_value::Value;
+
+static MIGRATIONS: EmbeddedMigrations = embed_migrations!("tests/migrations");
+
+async fn has_table(pool: &<Sqlite as Connector>::Pool, table: &str) -> bool {
+    let conn = pool.get().await.expect("conn");
+    let sql =
+        format!("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = '{table}'");
+    let mut stmt = conn.prepare(&sql).await.unwrap();
+    let rows = conn
+        .query(&mut stmt, vec![])
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    match rows[0].get(ColumnIndex::Index(0)).unwrap().to_owned() {
+        Value::BigInt(n) => n == 1,
+        v => panic!("unexpected value: {v:?}"),
+    }
+}
+
+#[tokio::test]
+async fn embedded_scripts() {
+    assert_eq!(
+        MigrationLoader::<Sqlite>::names(&MIGRATIONS),
+        ["0001_users", "0002_posts"]
+    );
+
+    let runner = MigrationLoader::<Sqlite>::load(&MIGRATIONS, Path::new("0001_users"))
+        .await
+        .unwrap();
+
+    let script = |system| Runner::<Sqlite>::script(&runner, system);
+    assert_eq!(
+        script(System::Mysql),
+        Some("CREATE TABLE users (id INT AUTO_INCREMENT PRIMARY KEY, name TEXT);\n")
+    );
+    assert_eq!(
+        script(System::Sqlite),
+        Some("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);\n")
+    );
+    assert!(Runner::<Sqlite>::reversible(&runner, System::Mysql));
+}
+
+#[tokio::test]
+async fn migrate_without_directory() {
+    let pool = Sqlite::create_pool(SqliteOptions::default())
+        .await
+        .expect("pool");
+
+    // Nothing is read from the filesystem, the directory doesn't exist
+    let migrator = Migrator::new(
+        pool.clone(),
+        MIGRATIONS,
+        "embedded".into(),
+        "migrations".to_string(),
+    );
+
+    let applied = async || {
+        migrator
+            .list_migrations()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| (m.name, m.applied.is_some()))
+            .collect::<Vec<_>>()
+    };
+
+    // One step at a time
+    assert!(migrator.migrate().await.unwrap());
+    assert_eq!(
+        applied().await,
+        [
+            ("0001_users".to_string(), true),
+            ("0002_posts".to_string(), false)
+        ]
+    );
+    assert!(has_table(&pool, "users").await);
+    assert!(!has_table(&pool, "posts").await);
+
+    assert!(migrator.migrate_all().await.unwrap());
+    assert_eq!(
+        applied().await,
+        [
+            ("0001_users".to_string(), true),
+            ("0002_posts".to_string(), true)
+        ]
+    );
+    assert!(has_table(&pool, "posts").await);
+}
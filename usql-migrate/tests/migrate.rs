@@ -0,0 +1,360 @@
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use futures::TryStreamExt;
+use usql_core::{ColumnIndex, Connector, Executor, Pool, Row, System};
+use usql_migrate::{
+    Drift, Error, MigrationLoader, Migrator, PlannedMigration, Runner, sql::SqlLoader,
+};
+use usql_sqlite::{Sqlite, SqliteOptions};
+use usql_value::Value;
+
+const USERS: &str = "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);";
+
+/// A temporary migrations directory, removed on drop
+struct Fixture {
+    dir: PathBuf,
+}
+
+impl Fixture {
+    fn new() -> Fixture {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let dir = std::env::temp_dir().join(format!(
+            "usql-migrate-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        Fixture { dir }
+    }
+
+    fn migration(&self, name: &str, files: &[(&str, &str)]) -> &Self {
+        let dir = self.dir.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file, content) in files {
+            std::fs::write(dir.join(file), content).unwrap();
+        }
+        self
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.dir).ok();
+    }
+}
+
+async fn migrator(fixture: &Fixture) -> (Migrator<Sqlite, SqlLoader>, <Sqlite as Connector>::Pool) {
+    let pool = Sqlite::create_pool(SqliteOptions::default())
+        .await
+        .expect("pool");
+
+    let migrator = Migrator::new(
+        pool.clone(),
+        SqlLoader,
+        fixture.dir.clone(),
+        "migrations".to_string(),
+    );
+
+    (migrator, pool)
+}
+
+async fn count(pool: &<Sqlite as Connector>::Pool, sql: &str) -> i64 {
+    let conn = pool.get().await.expect("conn");
+    let mut stmt = conn.prepare(sql).await.unwrap();
+    let rows = conn
+        .query(&mut stmt, vec![])
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    match rows[0].get(ColumnIndex::Index(0)).unwrap().to_owned() {
+        Value::BigInt(n) => n,
+        v => panic!("unexpected value: {v:?}"),
+    }
+}
+
+async fn has_table(pool: &<Sqlite as Connector>::Pool, table: &str) -> bool {
+    let sql =
+        format!("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = '{table}'");
+    count(pool, &sql).await == 1
+}
+
+#[tokio::test]
+async fn plan_per_dialect() {
+    let fixture = Fixture::new();
+    fixture.migration(
+        "0001_users",
+        &[
+            ("up.sql", USERS),
+            ("up.postgres", "CREATE TABLE users (id SERIAL PRIMARY KEY);"),
+            ("up.sqlite", "CREATE TABLE users (id INTEGER PRIMARY KEY);"),
+            (
+                "up.mysql",
+                "CREATE TABLE users (id INT AUTO_INCREMENT PRIMARY KEY);",
+            ),
+        ],
+    );
+
+    let runner = MigrationLoader::<Sqlite>::load(&SqlLoader, &fixture.dir.join("0001_users"))
+        .await
+        .unwrap();
+
+    let script = |system| Runner::<Sqlite>::script(&runner, system);
+    assert_eq!(
+        script(System::Postgres),
+        Some("CREATE TABLE users (id SERIAL PRIMARY KEY);")
+    );
+    assert_eq!(
+        script(System::Sqlite),
+        Some("CREATE TABLE users (id INTEGER PRIMARY KEY);")
+    );
+    assert_eq!(
+        script(System::Mysql),
+        Some("CREATE TABLE users (id INT AUTO_INCREMENT PRIMARY KEY);")
+    );
+    assert_eq!(script(System::LibSql), Some(USERS));
+}
+
+#[tokio::test]
+async fn plan_without_script() {
+    let fixture = Fixture::new();
+    fixture.migration(
+        "0001_users",
+        &[("up.postgres", "CREATE TABLE users (id SERIAL PRIMARY KEY);")],
+    );
+
+    let (migrator, _pool) = migrator(&fixture).await;
+
+    let plan = migrator.plan().await.unwrap();
+    assert_eq!(
+        plan,
+        vec![PlannedMigration {
+            name: "0001_users".to_string(),
+            script: None,
+        }]
+    );
+
+    assert!(migrator.migrate().await.is_err());
+}
+
+#[tokio::test]
+async fn dry_run_rolls_back() {
+    let fixture = Fixture::new();
+    fixture.migration(
+        "0001_users",
+        &[("up.sql", USERS), ("down.sql", "DROP TABLE users;")],
+    );
+
+    let (migrator, pool) = migrator(&fixture).await;
+
+    let plan = migrator.plan().await.unwrap();
+    assert_eq!(
+        plan,
+        vec![PlannedMigration {
+            name: "0001_users".to_string(),
+            script: Some(USERS.to_string()),
+        }]
+    );
+
+    assert_eq!(migrator.dry_run().await.unwrap(), plan);
+
+    assert!(!has_table(&pool, "users").await);
+    assert!(!has_table(&pool, "migrations").await);
+
+    assert!(migrator.migrate_all().await.unwrap());
+    assert!(has_table(&pool, "users").await);
+    assert_eq!(count(&pool, "SELECT COUNT(*) FROM migrations").await, 1);
+
+    assert!(migrator.dry_run().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn rollback_and_redo() {
+    let fixture = Fixture::new();
+    fixture
+        .migration(
+            "0001_users",
+            &[("up.sql", USERS), ("down.sql", "DROP TABLE users;")],
+        )
+        .migration(
+            "0002_posts",
+            &[
+                ("up.sql", "CREATE TABLE posts (id INTEGER PRIMARY KEY);"),
+                ("down.sql", "DROP TABLE posts;"),
+            ],
+        )
+        .migration(
+            "0003_tags",
+            &[("up.sql", "CREATE TABLE tags (id INTEGER PRIMARY KEY);")],
+        );
+
+    let (migrator, pool) = migrator(&fixture).await;
+
+    assert!(migrator.migrate_all().await.unwrap());
+    assert_eq!(count(&pool, "SELECT COUNT(*) FROM migrations").await, 3);
+
+    // 0003_tags has no down script
+    let err = migrator.rollback().await.unwrap_err();
+    assert!(matches!(err, Error::Irreversible(name) if name == "0003_tags"));
+    assert!(has_table(&pool, "tags").await);
+
+    std::fs::write(fixture.dir.join("0003_tags/down.sql"), "DROP TABLE tags;").unwrap();
+
+    assert!(migrator.rollback().await.unwrap());
+    assert!(!has_table(&pool, "tags").await);
+    assert_eq!(count(&pool, "SELECT COUNT(*) FROM migrations").await, 2);
+
+    assert!(migrator.redo().await.unwrap());
+    assert!(has_table(&pool, "posts").await);
+    assert_eq!(count(&pool, "SELECT COUNT(*) FROM migrations").await, 2);
+
+    assert!(migrator.rollback_to("0001_users").await.unwrap());
+    assert!(!has_table(&pool, "posts").await);
+    assert!(has_table(&pool, "users").await);
+    assert_eq!(count(&pool, "SELECT COUNT(*) FROM migrations").await, 1);
+
+    assert!(!migrator.rollback_to("0001_users").await.unwrap());
+    assert!(migrator.rollback_to("0002_posts").await.is_err());
+}
+
+#[tokio::test]
+async fn registered_migrations() {
+    let fixture = Fixture::new();
+    fixture
+        .migration(
+            "0001_users",
+            &[("up.sql", USERS), ("down.sql", "DROP TABLE users;")],
+        )
+        .migration(
+            "0003_posts",
+            &[
+                ("up.sql", "CREATE TABLE posts (id INTEGER PRIMARY KEY);"),
+                ("down.sql", "DROP TABLE posts;"),
+            ],
+        );
+
+    let pool = Sqlite::create_pool(SqliteOptions::default())
+        .await
+        .expect("pool");
+
+    let migrator = Migrator::new(
+        pool.clone(),
+        (SqlLoader,),
+        fixture.dir.clone(),
+        "migrations".to_string(),
+    )
+    .register_reversible(
+        "0002_backfill",
+        |exec| {
+            Box::pin(async move {
+                exec.exec_batch("INSERT INTO users (name) VALUES ('admin');")
+                    .await
+                    .map_err(Error::Connector)
+            })
+        },
+        |exec| {
+            Box::pin(async move {
+                exec.exec_batch("DELETE FROM users WHERE name = 'admin';")
+                    .await
+                    .map_err(Error::Connector)
+            })
+        },
+    )
+    .register("0004_touch", |exec| {
+        Box::pin(async move {
+            exec.exec_batch("UPDATE users SET name = 'root';")
+                .await
+                .map_err(Error::Connector)
+        })
+    });
+
+    let names = migrator
+        .list_migrations()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.name)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["0001_users", "0002_backfill", "0003_posts", "0004_touch"]
+    );
+
+    assert!(migrator.migrate_all().await.unwrap());
+    assert_eq!(
+        count(&pool, "SELECT COUNT(*) FROM users WHERE name = 'root'").await,
+        1
+    );
+
+    let err = migrator.rollback().await.unwrap_err();
+    assert!(matches!(err, Error::Irreversible(name) if name == "0004_touch"));
+
+    assert!(migrator.rollback_to("0001_users").await.is_err());
+    assert_eq!(count(&pool, "SELECT COUNT(*) FROM migrations").await, 4);
+}
+
+#[tokio::test]
+async fn verify_drift() {
+    let fixture = Fixture::new();
+    fixture
+        .migration("0001_users", &[("up.sql", USERS)])
+        .migration(
+            "0003_posts",
+            &[("up.sql", "CREATE TABLE posts (id INTEGER PRIMARY KEY);")],
+        )
+        .migration(
+            "0004_tags",
+            &[("up.sql", "CREATE TABLE tags (id INTEGER PRIMARY KEY);")],
+        );
+
+    let (migrator, _pool) = migrator(&fixture).await;
+    assert!(migrator.migrate_all().await.unwrap());
+    assert!(migrator.verify().await.unwrap().is_empty());
+
+    fixture
+        .migration(
+            "0001_users",
+            &[("up.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY);")],
+        )
+        .migration(
+            "0002_backfill",
+            &[("up.sql", "INSERT INTO users (name) VALUES ('admin');")],
+        );
+    std::fs::remove_dir_all(fixture.dir.join("0004_tags")).unwrap();
+
+    assert_eq!(
+        migrator.verify().await.unwrap(),
+        [
+            Drift::Changed("0001_users".to_string()),
+            Drift::Missing("0004_tags".to_string()),
+            Drift::OutOfOrder("0002_backfill".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn checksum_tags_scripts() {
+    let fixture = Fixture::new();
+    fixture
+        .migration("default", &[("up.sql", USERS)])
+        .migration("sqlite", &[("up.sqlite", USERS)])
+        .migration("both", &[("up.sql", USERS), ("up.sqlite", USERS)]);
+
+    let checksum = async |name: &str| {
+        let runner = MigrationLoader::<Sqlite>::load(&SqlLoader, &fixture.dir.join(name))
+            .await
+            .unwrap();
+        Runner::<Sqlite>::checksum(&runner).unwrap()
+    };
+
+    // The same script as the default or for one system is a different migration
+    let default = checksum("default").await;
+    assert_eq!(default, checksum("default").await);
+    assert_ne!(default, checksum("sqlite").await);
+    assert_ne!(checksum("sqlite").await, checksum("both").await);
+}
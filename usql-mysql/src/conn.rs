@@ -0,0 +1,161 @@
+use core::ops::{Deref, DerefMut};
+use std::sync::Mutex as SyncMutex;
+
+use async_lock::{Mutex, MutexGuard};
+use mysql_async::prelude::Queryable;
+use usql_core::{Connection, Connector, Executor, QueryResult, TransactionOptions};
+
+use crate::{
+    connector::Info, error::Error, pool::Recycle, row::Row, stmt::Statement,
+    transaction::Transaction, util::into_params,
+};
+
+use super::connector::Mysql;
+
+// mysql_async needs exclusive access to the connection for every command
+pub struct Conn {
+    // Only taken out when the connection is dropped
+    conn: Mutex<Option<mysql_async::Conn>>,
+    // Rollbacks queued by transactions dropped without being finished,
+    // they run before the next command on the connection
+    pending: SyncMutex<Vec<String>>,
+    recycle: Recycle,
+}
+
+impl Conn {
+    pub(crate) fn new(conn: mysql_async::Conn, recycle: Recycle) -> Conn {
+        Conn {
+            conn: Mutex::new(Some(conn)),
+            pending: SyncMutex::new(Vec::new()),
+            recycle,
+        }
+    }
+
+    pub(crate) async fn lock(&self) -> Result<ConnGuard<'_>, Error> {
+        let mut conn = ConnGuard(self.conn.lock().await);
+
+        let pending = core::mem::take(&mut *self.pending.lock().expect("pending"));
+        for stmt in pending {
+            conn.query_drop(stmt).await?;
+        }
+
+        Ok(conn)
+    }
+
+    pub(crate) fn defer(&self, stmt: String) {
+        self.pending.lock().expect("pending").push(stmt);
+    }
+}
+
+impl Drop for Conn {
+    fn drop(&mut self) {
+        let pending = core::mem::take(self.pending.get_mut().expect("pending"));
+        if pending.is_empty() {
+            return;
+        }
+
+        // Returning the connection to mysql_async now would hand out the
+        // unfinished transaction, so the pool flushes it first
+        if let Some(conn) = self.conn.get_mut().take() {
+            self.recycle.push(conn, pending);
+        }
+    }
+}
+
+pub(crate) struct ConnGuard<'a>(MutexGuard<'a, Option<mysql_async::Conn>>);
+
+impl Deref for ConnGuard<'_> {
+    type Target = mysql_async::Conn;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref().expect("connection")
+    }
+}
+
+impl DerefMut for ConnGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.as_mut().expect("connection")
+    }
+}
+
+impl Executor for Conn {
+    type Connector = Mysql;
+
+    fn db_info(&self) -> <Self::Connector as usql_core::Connector>::Info {
+        Info
+    }
+
+    fn prepare<'a>(
+        &'a self,
+        query: &'a str,
+    ) -> impl Future<
+        Output = Result<
+            <Self::Connector as Connector>::Statement,
+            <Self::Connector as Connector>::Error,
+        >,
+    > + Send
+    + 'a {
+        async move {
+            let mut conn = self.lock().await?;
+            Ok(Statement(conn.prep(query).await?))
+        }
+    }
+
+    fn query<'a>(
+        &'a self,
+        stmt: &'a mut <Self::Connector as Connector>::Statement,
+        params: Vec<usql_value::ValueCow<'a>>,
+    ) -> usql_core::QueryStream<'a, Self::Connector> {
+        let stream = async_stream::try_stream! {
+            let mut conn = self.lock().await?;
+            let mut result = conn.exec_iter(stmt.0.clone(), into_params(params)).await?;
+
+            while let Some(next) = result.next().await? {
+                yield Row(next);
+            }
+        };
+
+        Box::pin(stream)
+    }
+
+    fn exec<'a>(
+        &'a self,
+        stmt: &'a mut <Self::Connector as Connector>::Statement,
+        params: Vec<usql_value::ValueCow<'a>>,
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
+        async move {
+            let mut conn = self.lock().await?;
+            conn.exec_drop(stmt.0.clone(), into_params(params)).await?;
+            Ok(QueryResult::new(
+                conn.affected_rows(),
+                conn.last_insert_id().map(|id| id as i64),
+            ))
+        }
+    }
+
+    fn exec_batch<'a>(
+        &'a self,
+        stmt: &'a str,
+    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
+        async move {
+            self.lock().await?.query_drop(stmt).await?;
+            Ok(())
+        }
+    }
+}
+
+impl Connection for Conn {
+    type Transaction<'conn>
+        = Transaction<'conn>
+    where
+        Self: 'conn;
+
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<Self::Transaction<'_>, <Self::Connector as Connector>::Error>> + Send
+    {
+        Transaction::begin(self, options)
+    }
+}
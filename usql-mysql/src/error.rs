@@ -0,0 +1,128 @@
+use core::fmt;
+
+use usql_core::{DatabaseError, ErrorKind};
+use usql_value::Type;
+
+#[derive(Debug)]
+pub enum Error {
+    Mysql(mysql_async::Error),
+    NotFound,
+    Convert {
+        found: Option<Type>,
+        expected: Type,
+    },
+    /// A value from the server with no usql equivalent, eg. a zero date
+    InvalidValue(mysql_async::Value),
+}
+
+impl Error {
+    /// The server error code, eg. 1062 for a duplicate key
+    pub fn code(&self) -> Option<u16> {
+        match self {
+            Self::Mysql(mysql_async::Error::Server(err)) => Some(err.code),
+            _ => None,
+        }
+    }
+}
+
+impl From<mysql_async::Error> for Error {
+    fn from(value: mysql_async::Error) -> Self {
+        Error::Mysql(value)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mysql(err) => write!(f, "{}", err),
+            Self::NotFound => write!(f, "not found"),
+            Self::InvalidValue(value) => write!(f, "invalid value: {value:?}"),
+            Self::Convert { found, expected } => {
+                write!(
+                    f,
+                    "Convert: found: {}, expected {}",
+                    found
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| "null".to_string()),
+                    expected
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Mysql(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Error {
+    fn message(&self) -> Option<&str> {
+        match self {
+            Self::Mysql(mysql_async::Error::Server(err)) => Some(&err.message),
+            _ => None,
+        }
+    }
+}
+
+// The text between `start` and the next `end`
+fn between<'a>(msg: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = msg.split_once(start)?;
+    rest.split_once(end).map(|(value, _)| value)
+}
+
+impl DatabaseError for Error {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Mysql(mysql_async::Error::Io(_))
+            | Self::Mysql(mysql_async::Error::Driver(mysql_async::DriverError::ConnectionClosed)) => {
+                return ErrorKind::ConnectionLost;
+            }
+            _ => {}
+        }
+
+        match self.code() {
+            // ER_DUP_ENTRY, ER_DUP_KEY
+            Some(1062 | 1022) => ErrorKind::UniqueViolation,
+            // ER_NO_REFERENCED_ROW(_2), ER_ROW_IS_REFERENCED(_2)
+            Some(1216 | 1217 | 1451 | 1452) => ErrorKind::ForeignKeyViolation,
+            // ER_BAD_NULL_ERROR
+            Some(1048) => ErrorKind::NotNullViolation,
+            // ER_CHECK_CONSTRAINT_VIOLATED
+            Some(3819) => ErrorKind::CheckViolation,
+            // ER_LOCK_DEADLOCK
+            Some(1213) => ErrorKind::SerializationFailure,
+            // ER_LOCK_WAIT_TIMEOUT
+            Some(1205) => ErrorKind::Busy,
+            // ER_PARSE_ERROR
+            Some(1064) => ErrorKind::Syntax,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    fn constraint(&self) -> Option<&str> {
+        let msg = self.message()?;
+        match self.kind() {
+            // Duplicate entry 'a' for key 'users.email'
+            ErrorKind::UniqueViolation => between(msg, "for key '", "'"),
+            // ... a foreign key constraint fails (`db`.`posts`, CONSTRAINT `fk_user` FOREIGN KEY ...
+            ErrorKind::ForeignKeyViolation => between(msg, "CONSTRAINT `", "`"),
+            // Check constraint 'positive_age' is violated.
+            ErrorKind::CheckViolation => between(msg, "constraint '", "'"),
+            _ => None,
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        let msg = self.message()?;
+        match self.kind() {
+            ErrorKind::ForeignKeyViolation => between(msg, "`.`", "`"),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+mod conn;
+mod connector;
+mod error;
+mod options;
+mod pool;
+mod row;
+mod stmt;
+mod transaction;
+mod util;
+
+pub use self::{
+    conn::Conn as MysqlConn, connector::Info as MysqlDatabaseInfo, connector::Mysql,
+    error::Error as MysqlError, options::Options as MysqlOptions, pool::Pool as MysqlPool,
+    row::Row as MysqlRow, stmt::Statement as MysqlStatement,
+    transaction::Transaction as MysqlTransaction,
+};
+
+pub use mysql_async::{OptsBuilder, PoolConstraints, PoolOpts};
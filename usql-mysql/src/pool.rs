@@ -0,0 +1,96 @@
+use std::sync::{Arc, Mutex};
+
+use mysql_async::prelude::Queryable;
+
+use crate::{conn::Conn, connector::Mysql};
+
+#[derive(Clone)]
+pub struct Pool {
+    pool: mysql_async::Pool,
+    recycle: Recycle,
+}
+
+impl Pool {
+    pub(crate) fn new(pool: mysql_async::Pool, max: usize) -> Pool {
+        Pool {
+            pool,
+            recycle: Recycle::new(max),
+        }
+    }
+}
+
+impl usql_core::Pool for Pool {
+    type Connector = Mysql;
+
+    fn get(
+        &self,
+    ) -> impl Future<
+        Output = Result<
+            <Self::Connector as usql_core::Connector>::Connection,
+            <Self::Connector as usql_core::Connector>::Error,
+        >,
+    > + Send
+    + '_ {
+        async move {
+            while let Some((mut conn, pending)) = self.recycle.pop() {
+                if flush(&mut conn, pending).await.is_ok() {
+                    return Ok(Conn::new(conn, self.recycle.clone()));
+                }
+                // Never hand a connection that failed to roll back to the pool
+                let _ = conn.disconnect().await;
+            }
+
+            let conn = self.pool.get_conn().await?;
+            Ok(Conn::new(conn, self.recycle.clone()))
+        }
+    }
+}
+
+async fn flush(
+    conn: &mut mysql_async::Conn,
+    pending: Vec<String>,
+) -> Result<(), mysql_async::Error> {
+    for stmt in pending {
+        conn.query_drop(stmt).await?;
+    }
+    Ok(())
+}
+
+/// Connections dropped with rollbacks still queued.
+///
+/// They are held here instead of going back to the mysql_async pool and the
+/// next [`Pool::get`] finishes the rollbacks before handing them out again.
+/// Holds at most as many connections as the pool has, any more are closed,
+/// which rolls back on the server.
+#[derive(Clone)]
+pub(crate) struct Recycle {
+    conns: Arc<Mutex<Vec<(mysql_async::Conn, Vec<String>)>>>,
+    max: usize,
+}
+
+impl Recycle {
+    fn new(max: usize) -> Recycle {
+        Recycle {
+            conns: Arc::default(),
+            max,
+        }
+    }
+
+    pub(crate) fn push(&self, conn: mysql_async::Conn, pending: Vec<String>) {
+        let mut conns = self.conns.lock().expect("recycle");
+        if conns.len() < self.max {
+            conns.push((conn, pending));
+            return;
+        }
+        drop(conns);
+
+        // mysql_async connections only live inside a tokio runtime
+        tokio::spawn(async move {
+            let _ = conn.disconnect().await;
+        });
+    }
+
+    fn pop(&self) -> Option<(mysql_async::Conn, Vec<String>)> {
+        self.conns.lock().expect("recycle").pop()
+    }
+}
@@ -0,0 +1,107 @@
+use usql_core::{ColumnIndex, Connector};
+use usql_value::{JsonValue, Type, Value, ValueCow, ValueRef};
+
+use crate::{connector::Mysql, error::Error, util::mysql_to_usql};
+
+pub struct Row(pub(crate) mysql_async::Row);
+
+impl Row {
+    fn index(&self, index: ColumnIndex<'_>) -> Option<usize> {
+        match index {
+            ColumnIndex::Index(idx) => (idx < self.0.len()).then_some(idx),
+            ColumnIndex::Named(name) => self
+                .0
+                .columns_ref()
+                .iter()
+                .position(|m| m.name_ref() == name.as_bytes()),
+        }
+    }
+}
+
+impl usql_core::Row for Row {
+    type Connector = Mysql;
+
+    fn get<'a>(
+        &'a self,
+        index: ColumnIndex<'_>,
+    ) -> Result<ValueCow<'a>, <Self::Connector as Connector>::Error> {
+        let idx = self.index(index).ok_or(Error::NotFound)?;
+        let value = self.0.as_ref(idx).ok_or(Error::NotFound)?;
+        Ok(ValueCow::Owned(mysql_to_usql(
+            value,
+            &self.0.columns_ref()[idx],
+        )?))
+    }
+
+    fn get_typed<'a>(
+        &'a self,
+        index: ColumnIndex<'_>,
+        ty: Type,
+    ) -> Result<ValueCow<'a>, <Self::Connector as Connector>::Error> {
+        let value = usql_core::Row::get(self, index)?;
+        get_typed(value, ty)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn column_name(&self, idx: usize) -> Option<&str> {
+        self.0
+            .columns_ref()
+            .get(idx)
+            .and_then(|m| core::str::from_utf8(m.name_ref()).ok())
+    }
+}
+
+// MySQL returns every integer as a 64 bit value and has no native
+// bool, uuid or json on the wire, so narrow those here
+fn get_typed<'a>(value: ValueCow<'a>, ty: Type) -> Result<ValueCow<'a>, Error> {
+    let convert_err = |value: &ValueCow<'a>, expected: Type| Error::Convert {
+        found: value.as_ref().get_type(),
+        expected,
+    };
+
+    let value = match (&ty, value.as_ref()) {
+        (Type::Any, _) | (_, ValueRef::Null) => value,
+        (Type::BigInt, ValueRef::BigInt(_))
+        | (Type::Float, ValueRef::Float(_))
+        | (Type::Double, ValueRef::Double(_))
+        | (Type::Text, ValueRef::Text(_))
+        | (Type::Blob, ValueRef::ByteArray(_))
+        | (Type::Date, ValueRef::Date(_))
+        | (Type::Time, ValueRef::Time(_))
+        | (Type::DateTime, ValueRef::Timestamp(_))
+        | (Type::Json, ValueRef::Json(_)) => value,
+        (Type::SmallInt, ValueRef::BigInt(i)) => Value::SmallInt(
+            i.try_into()
+                .map_err(|_| convert_err(&value, Type::SmallInt))?,
+        )
+        .into(),
+        (Type::Int, ValueRef::BigInt(i)) => {
+            Value::Int(i.try_into().map_err(|_| convert_err(&value, Type::Int))?).into()
+        }
+        (Type::Bool, ValueRef::BigInt(i)) => Value::Bool(i != 0).into(),
+        (Type::Float, ValueRef::Double(f)) => Value::Float((*f as f32).into()).into(),
+        (Type::Double, ValueRef::Float(f)) => Value::Double((*f as f64).into()).into(),
+        (Type::Uuid, ValueRef::ByteArray(bs)) => {
+            let id = uuid::Uuid::from_slice(bs).map_err(|_| convert_err(&value, Type::Uuid))?;
+            Value::Uuid(id).into()
+        }
+        (Type::Uuid, ValueRef::Text(text)) => {
+            let id = uuid::Uuid::parse_str(text).map_err(|_| convert_err(&value, Type::Uuid))?;
+            Value::Uuid(id).into()
+        }
+        (Type::Json, ValueRef::Text(text)) => {
+            let json: JsonValue =
+                serde_json::from_str(text).map_err(|_| convert_err(&value, Type::Json))?;
+            Value::Json(json).into()
+        }
+        (Type::DateTime, ValueRef::Date(date)) => {
+            Value::Timestamp(date.and_hms_opt(0, 0, 0).expect("midnight")).into()
+        }
+        _ => return Err(convert_err(&value, ty)),
+    };
+
+    Ok(value)
+}
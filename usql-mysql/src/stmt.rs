@@ -0,0 +1,12 @@
+use crate::connector::Mysql;
+
+pub struct Statement(pub(crate) mysql_async::Statement);
+
+impl usql_core::Statement for Statement {
+    type Connector = Mysql;
+
+    // Statements live in the connection's statement cache and are closed when evicted
+    fn finalize(self) -> Result<(), <Self::Connector as usql_core::Connector>::Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+use mysql_async::prelude::Queryable;
+use usql_core::{Connector, Executor, IsolationLevel, QueryResult, TransactionOptions};
+
+use crate::{
+    conn::Conn,
+    connector::{Info, Mysql},
+    error::Error,
+};
+
+/// A transaction, or a savepoint nested inside one.
+///
+/// Dropping it without committing or rolling back queues the rollback,
+/// which is then run before the next command on the connection, or by the
+/// pool before the connection is handed out again.
+pub struct Transaction<'conn> {
+    conn: &'conn Conn,
+    depth: usize,
+    done: bool,
+}
+
+impl<'conn> Transaction<'conn> {
+    pub(crate) async fn begin(
+        conn: &'conn Conn,
+        options: TransactionOptions,
+    ) -> Result<Transaction<'conn>, Error> {
+        let mut guard = conn.lock().await?;
+
+        // Applies to the next transaction only
+        if let Some(level) = options.isolation_level {
+            let level = match level {
+                IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+                IsolationLevel::ReadCommitted => "READ COMMITTED",
+                IsolationLevel::RepeatableRead => "REPEATABLE READ",
+                IsolationLevel::Serializable => "SERIALIZABLE",
+            };
+            guard
+                .query_drop(format!("SET TRANSACTION ISOLATION LEVEL {level}"))
+                .await?;
+        }
+
+        let stmt = match options.read_only {
+            true => "START TRANSACTION READ ONLY",
+            false => "START TRANSACTION",
+        };
+        if let Err(err) = guard.query_drop(stmt).await {
+            // The level would otherwise apply to whatever transaction runs next
+            if options.isolation_level.is_some() {
+                let _ = reset_isolation_level(&mut guard).await;
+            }
+            return Err(err.into());
+        }
+        drop(guard);
+
+        Ok(Transaction {
+            conn,
+            depth: 0,
+            done: false,
+        })
+    }
+
+    fn commit_stmts(&self) -> Vec<String> {
+        match self.depth {
+            0 => vec!["COMMIT".into()],
+            _ => vec![format!("RELEASE SAVEPOINT {}", savepoint_name(self.depth))],
+        }
+    }
+
+    fn rollback_stmts(&self) -> Vec<String> {
+        match self.depth {
+            0 => vec!["ROLLBACK".into()],
+            _ => vec![
+                format!("ROLLBACK TO SAVEPOINT {}", savepoint_name(self.depth)),
+                format!("RELEASE SAVEPOINT {}", savepoint_name(self.depth)),
+            ],
+        }
+    }
+
+    async fn finish(mut self, stmts: Vec<String>) -> Result<(), Error> {
+        let mut conn = self.conn.lock().await?;
+        for stmt in stmts {
+            conn.query_drop(stmt).await?;
+        }
+        self.done = true;
+        Ok(())
+    }
+}
+
+// Back to the session's level, eg. `REPEATABLE-READ`
+async fn reset_isolation_level(conn: &mut mysql_async::Conn) -> Result<(), mysql_async::Error> {
+    let level: Option<String> = conn
+        .query_first("SELECT @@SESSION.transaction_isolation")
+        .await?;
+
+    if let Some(level) = level {
+        conn.query_drop(format!(
+            "SET TRANSACTION ISOLATION LEVEL {}",
+            level.replace('-', " ")
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn savepoint_name(depth: usize) -> String {
+    format!("usql_sp_{depth}")
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            for stmt in self.rollback_stmts() {
+                self.conn.defer(stmt);
+            }
+        }
+    }
+}
+
+impl<'conn> usql_core::Transaction<'conn> for Transaction<'conn> {
+    type Savepoint<'a>
+        = Transaction<'a>
+    where
+        Self: 'a;
+
+    fn savepoint(
+        &mut self,
+    ) -> impl Future<
+        Output = Result<Self::Savepoint<'_>, <Self::Connector as usql_core::Connector>::Error>,
+    > + Send {
+        async move {
+            let depth = self.depth + 1;
+            self.conn
+                .lock()
+                .await?
+                .query_drop(format!("SAVEPOINT {}", savepoint_name(depth)))
+                .await?;
+
+            Ok(Transaction {
+                conn: self.conn,
+                depth,
+                done: false,
+            })
+        }
+    }
+
+    fn commit(
+        self,
+    ) -> impl Future<Output = Result<(), <Self::Connector as usql_core::Connector>::Error>> + Send
+    {
+        async move {
+            let stmts = self.commit_stmts();
+            self.finish(stmts).await
+        }
+    }
+
+    fn rollback(
+        self,
+    ) -> impl Future<Output = Result<(), <Self::Connector as usql_core::Connector>::Error>> + Send
+    {
+        async move {
+            let stmts = self.rollback_stmts();
+            self.finish(stmts).await
+        }
+    }
+}
+
+impl<'conn> Executor for Transaction<'conn> {
+    type Connector = Mysql;
+
+    fn db_info(&self) -> <Self::Connector as usql_core::Connector>::Info {
+        Info
+    }
+
+    fn prepare<'a>(
+        &'a self,
+        query: &'a str,
+    ) -> impl Future<
+        Output = Result<
+            <Self::Connector as usql_core::Connector>::Statement,
+            <Self::Connector as usql_core::Connector>::Error,
+        >,
+    > + Send
+    + 'a {
+        self.conn.prepare(query)
+    }
+
+    fn query<'a>(
+        &'a self,
+        stmt: &'a mut <Self::Connector as usql_core::Connector>::Statement,
+        params: Vec<usql_value::ValueCow<'a>>,
+    ) -> usql_core::QueryStream<'a, Self::Connector> {
+        self.conn.query(stmt, params)
+    }
+
+    fn exec<'a>(
+        &'a self,
+        stmt: &'a mut <Self::Connector as usql_core::Connector>::Statement,
+        params: Vec<usql_value::ValueCow<'a>>,
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
+        self.conn.exec(stmt, params)
+    }
+
+    fn exec_batch<'a>(
+        &'a self,
+        stmt: &'a str,
+    ) -> impl Future<Output = Result<(), <Self::Connector as usql_core::Connector>::Error>> + Send + 'a
+    {
+        self.conn.exec_batch(stmt)
+    }
+}
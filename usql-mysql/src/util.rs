@@ -0,0 +1,119 @@
+use chrono::{Datelike, NaiveDate, NaiveTime, Timelike};
+use mysql_async::{Column, Params, consts::ColumnType};
+use usql_value::{JsonValue, Value, ValueCow};
+
+use crate::error::Error;
+
+// Collation id of the `binary` character set, used by BLOB/BINARY columns
+const BINARY_CHARSET: u16 = 63;
+
+pub fn into_params(params: Vec<ValueCow<'_>>) -> Params {
+    if params.is_empty() {
+        return Params::Empty;
+    }
+
+    Params::Positional(
+        params
+            .into_iter()
+            .map(|m| usql_to_mysql(m.to_owned()))
+            .collect(),
+    )
+}
+
+pub fn usql_to_mysql(value: Value) -> mysql_async::Value {
+    match value {
+        Value::Null => mysql_async::Value::NULL,
+        Value::Bool(b) => mysql_async::Value::Int(b as _),
+        Value::SmallInt(n) => mysql_async::Value::Int(n as _),
+        Value::Int(n) => mysql_async::Value::Int(n as _),
+        Value::BigInt(n) => mysql_async::Value::Int(n),
+        Value::Float(f) => mysql_async::Value::Float(*f),
+        Value::Double(f) => mysql_async::Value::Double(*f),
+        Value::Text(s) => {
+            let s: String = s.into();
+            mysql_async::Value::Bytes(s.into_bytes())
+        }
+        Value::ByteArray(b) => mysql_async::Value::Bytes(b.to_vec()),
+        Value::Date(d) => {
+            mysql_async::Value::Date(d.year() as _, d.month() as _, d.day() as _, 0, 0, 0, 0)
+        }
+        Value::Time(t) => mysql_async::Value::Time(
+            false,
+            0,
+            t.hour() as _,
+            t.minute() as _,
+            t.second() as _,
+            t.nanosecond() / 1000,
+        ),
+        Value::Timestamp(dt) => mysql_async::Value::Date(
+            dt.year() as _,
+            dt.month() as _,
+            dt.day() as _,
+            dt.hour() as _,
+            dt.minute() as _,
+            dt.second() as _,
+            dt.nanosecond() / 1000,
+        ),
+        Value::Uuid(u) => mysql_async::Value::Bytes(u.as_bytes().to_vec()),
+        Value::Json(json) => {
+            let string = serde_json::to_string(&json).expect("json encode");
+            mysql_async::Value::Bytes(string.into_bytes())
+        }
+        Value::Array(list) => {
+            let string = serde_json::to_string(&list).expect("json encode");
+            mysql_async::Value::Bytes(string.into_bytes())
+        }
+        Value::Geometry(geo) => mysql_async::Value::Bytes(geo.to_string().into_bytes()),
+    }
+}
+
+pub fn mysql_to_usql(value: &mysql_async::Value, column: &Column) -> Result<Value, Error> {
+    let invalid = || Error::InvalidValue(value.clone());
+
+    let value = match value {
+        mysql_async::Value::NULL => Value::Null,
+        mysql_async::Value::Int(i) => Value::BigInt(*i),
+        // BIGINT UNSIGNED can go past i64::MAX
+        mysql_async::Value::UInt(i) => Value::BigInt(i64::try_from(*i).map_err(|_| invalid())?),
+        mysql_async::Value::Float(f) => Value::Float((*f).into()),
+        mysql_async::Value::Double(f) => Value::Double((*f).into()),
+        mysql_async::Value::Date(year, month, day, hour, min, sec, micros) => {
+            // Zero dates (0000-00-00) have no chrono equivalent
+            let date =
+                NaiveDate::from_ymd_opt(*year as _, *month as _, *day as _).ok_or_else(invalid)?;
+
+            if column.column_type() == ColumnType::MYSQL_TYPE_DATE {
+                return Ok(Value::Date(date));
+            }
+
+            date.and_hms_micro_opt(*hour as _, *min as _, *sec as _, *micros)
+                .map(Value::Timestamp)
+                .ok_or_else(invalid)?
+        }
+        // TIME is a duration of up to 838 hours either way, only
+        // times of day fit in a NaiveTime
+        mysql_async::Value::Time(true, ..) | mysql_async::Value::Time(_, 1.., ..) => {
+            return Err(invalid());
+        }
+        mysql_async::Value::Time(false, 0, hour, min, sec, micros) => {
+            NaiveTime::from_hms_micro_opt(*hour as _, *min as _, *sec as _, *micros)
+                .map(Value::Time)
+                .ok_or_else(invalid)?
+        }
+        mysql_async::Value::Bytes(bytes) => {
+            if column.column_type() == ColumnType::MYSQL_TYPE_JSON {
+                if let Ok(json) = serde_json::from_slice::<JsonValue>(bytes) {
+                    return Ok(Value::Json(json));
+                }
+            }
+
+            if column.character_set() == BINARY_CHARSET {
+                Value::ByteArray(bytes.clone().into())
+            } else {
+                Value::Text(String::from_utf8_lossy(bytes).as_ref().into())
+            }
+        }
+    };
+
+    Ok(value)
+}
@@ -0,0 +1,285 @@
+mod harness;
+
+use futures::TryStreamExt;
+use usql_core::{
+    ColumnIndex, Connection, DatabaseError, ErrorKind, Executor, IsolationLevel, Pool, Row,
+    Transaction, TransactionOptions,
+};
+use usql_mysql::MysqlError;
+use usql_value::{Type, Value};
+
+use harness::{count, with_database};
+
+const USERS: &str =
+    "CREATE TABLE users (id BIGINT AUTO_INCREMENT PRIMARY KEY, name VARCHAR(255) NOT NULL)";
+
+#[tokio::test]
+#[ignore = "needs mysql, USQL_MYSQL_URL"]
+async fn roundtrip() {
+    with_database(|pool| async move {
+        let conn = pool.get().await.unwrap();
+
+        conn.exec_batch(
+            "CREATE TABLE users (id BIGINT AUTO_INCREMENT PRIMARY KEY, name VARCHAR(255) NOT NULL, age BIGINT, active BOOL)",
+        )
+        .await
+        .unwrap();
+
+        let mut stmt = conn
+            .prepare("INSERT INTO users (name, age, active) VALUES (?, ?, ?)")
+            .await
+            .unwrap();
+
+        let ret = conn
+            .exec(
+                &mut stmt,
+                vec![
+                    Value::from("Rasmus").into(),
+                    Value::Int(42).into(),
+                    Value::Bool(true).into(),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(ret.affected_rows, 1);
+        assert_eq!(ret.last_insert_id, Some(1));
+
+        let mut stmt = conn
+            .prepare("SELECT id, name, age, active FROM users")
+            .await
+            .unwrap();
+
+        let rows = conn
+            .query(&mut stmt, vec![])
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(rows.len(), 1);
+        let row = &rows[0];
+
+        assert_eq!(row.len(), 4);
+        assert_eq!(row.column_name(1), Some("name"));
+        assert_eq!(
+            row.get(ColumnIndex::Named("id")).unwrap().to_owned(),
+            Value::BigInt(1)
+        );
+        assert_eq!(
+            row.get(ColumnIndex::Named("name")).unwrap().to_owned(),
+            Value::from("Rasmus")
+        );
+        assert_eq!(
+            row.get_typed(ColumnIndex::Named("age"), Type::Int)
+                .unwrap()
+                .to_owned(),
+            Value::Int(42)
+        );
+        assert!(matches!(
+            row.get(ColumnIndex::Named("missing")),
+            Err(MysqlError::NotFound)
+        ));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "needs mysql, USQL_MYSQL_URL"]
+async fn transactions() {
+    with_database(|pool| async move {
+        let mut conn = pool.get().await.unwrap();
+
+        conn.exec_batch(USERS).await.unwrap();
+
+        let trans = conn.begin().await.unwrap();
+        trans
+            .exec_batch("INSERT INTO users (name) VALUES ('rollback')")
+            .await
+            .unwrap();
+        assert_eq!(count(&trans, "users").await, 1);
+        trans.rollback().await.unwrap();
+
+        assert_eq!(count(&conn, "users").await, 0);
+
+        let trans = conn.begin().await.unwrap();
+        trans
+            .exec_batch("INSERT INTO users (name) VALUES ('commit')")
+            .await
+            .unwrap();
+        trans.commit().await.unwrap();
+
+        assert_eq!(count(&conn, "users").await, 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "needs mysql, USQL_MYSQL_URL"]
+async fn savepoints() {
+    with_database(|pool| async move {
+        let mut conn = pool.get().await.unwrap();
+
+        conn.exec_batch(USERS).await.unwrap();
+
+        let mut trans = conn.begin().await.unwrap();
+        trans
+            .exec_batch("INSERT INTO users (name) VALUES ('outer')")
+            .await
+            .unwrap();
+
+        let savepoint = trans.savepoint().await.unwrap();
+        savepoint
+            .exec_batch("INSERT INTO users (name) VALUES ('rollback')")
+            .await
+            .unwrap();
+        assert_eq!(count(&savepoint, "users").await, 2);
+        savepoint.rollback().await.unwrap();
+
+        assert_eq!(count(&trans, "users").await, 1);
+
+        let mut savepoint = trans.savepoint().await.unwrap();
+        savepoint
+            .exec_batch("INSERT INTO users (name) VALUES ('release')")
+            .await
+            .unwrap();
+        let nested = savepoint.savepoint().await.unwrap();
+        nested
+            .exec_batch("INSERT INTO users (name) VALUES ('nested')")
+            .await
+            .unwrap();
+        nested.commit().await.unwrap();
+        savepoint.commit().await.unwrap();
+
+        trans.commit().await.unwrap();
+
+        assert_eq!(count(&conn, "users").await, 3);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "needs mysql, USQL_MYSQL_URL"]
+async fn dropped_transactions_roll_back() {
+    with_database(|pool| async move {
+        let mut conn = pool.get().await.unwrap();
+
+        conn.exec_batch(USERS).await.unwrap();
+
+        // Rolled back before the next command on the connection
+        let trans = conn.begin().await.unwrap();
+        trans
+            .exec_batch("INSERT INTO users (name) VALUES ('dropped')")
+            .await
+            .unwrap();
+        drop(trans);
+
+        assert_eq!(count(&conn, "users").await, 0);
+
+        // Rolled back by the pool before the connection is handed out again
+        let trans = conn.begin().await.unwrap();
+        trans
+            .exec_batch("INSERT INTO users (name) VALUES ('dropped')")
+            .await
+            .unwrap();
+        drop(trans);
+        drop(conn);
+
+        assert_eq!(count(&pool.get().await.unwrap(), "users").await, 0);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "needs mysql, USQL_MYSQL_URL"]
+async fn transaction_options() {
+    with_database(|pool| async move {
+        let mut conn = pool.get().await.unwrap();
+
+        conn.exec_batch(USERS).await.unwrap();
+
+        let options = TransactionOptions::new()
+            .isolation_level(IsolationLevel::Serializable)
+            .read_only(true);
+
+        let trans = conn.begin_with(options).await.unwrap();
+        let err = trans
+            .exec_batch("INSERT INTO users (name) VALUES ('read only')")
+            .await
+            .unwrap_err();
+        // ER_CANT_EXECUTE_IN_READ_ONLY_TRANSACTION
+        assert_eq!(err.code(), Some(1792));
+        trans.rollback().await.unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "needs mysql, USQL_MYSQL_URL"]
+async fn errors() {
+    with_database(|pool| async move {
+        let conn = pool.get().await.unwrap();
+
+        conn.exec_batch(
+            "CREATE TABLE users (id BIGINT AUTO_INCREMENT PRIMARY KEY, name VARCHAR(255) UNIQUE)",
+        )
+        .await
+        .unwrap();
+
+        conn.exec_batch("INSERT INTO users (name) VALUES ('dup')")
+            .await
+            .unwrap();
+
+        let err = conn
+            .exec_batch("INSERT INTO users (name) VALUES ('dup')")
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), Some(1062));
+        assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+        assert_eq!(err.constraint(), Some("users.name"));
+
+        let err = conn.exec_batch("SELEC 1").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Syntax);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[ignore = "needs mysql, USQL_MYSQL_URL"]
+async fn invalid_values() {
+    with_database(|pool| async move {
+        let conn = pool.get().await.unwrap();
+
+        // Zero dates are rejected in the default strict mode
+        conn.exec_batch(
+            "SET SESSION sql_mode = '';
+            CREATE TABLE dates (day DATE);
+            INSERT INTO dates (day) VALUES ('0000-00-00');",
+        )
+        .await
+        .unwrap();
+
+        for sql in [
+            "SELECT day FROM dates",
+            "SELECT CAST(18446744073709551615 AS UNSIGNED)",
+            "SELECT CAST('-01:00:00' AS TIME)",
+            "SELECT CAST('30:00:00' AS TIME)",
+        ] {
+            let mut stmt = conn.prepare(sql).await.unwrap();
+            let rows = conn
+                .query(&mut stmt, vec![])
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+
+            assert!(
+                matches!(
+                    rows[0].get(ColumnIndex::Index(0)),
+                    Err(MysqlError::InvalidValue(_))
+                ),
+                "{sql}"
+            );
+        }
+    })
+    .await;
+}
@@ -1,4 +1,5 @@
-use usql_core::{Connection, Connector, Executor};
+use tokio_postgres::IsolationLevel as PgIsolationLevel;
+use usql_core::{Connection, Connector, Executor, IsolationLevel, QueryResult, TransactionOptions};
 
 use crate::{connector::Info, row::Row, stmt::Statement, transaction::Transaction};
 
@@ -50,12 +51,14 @@ impl Executor for Conn {
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: Vec<usql_value::ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
         async move {
-            self.0
+            let affected_rows = self
+                .0
                 .execute_raw(&stmt.0, params.into_iter().map(|m| m.to_owned()))
                 .await?;
-            Ok(())
+            Ok(QueryResult::new(affected_rows, None))
         }
     }
 
@@ -76,13 +79,33 @@ impl Connection for Conn {
     where
         Self: 'conn;
 
-    fn begin(
+    fn begin_with(
         &mut self,
+        options: TransactionOptions,
     ) -> impl Future<Output = Result<Self::Transaction<'_>, <Self::Connector as Connector>::Error>> + Send
     {
         async move {
-            let trans = self.0.transaction().await?;
-            Ok(Transaction(trans))
+            let mut builder = self.0.build_transaction();
+
+            if let Some(level) = options.isolation_level {
+                builder = builder.isolation_level(match level {
+                    IsolationLevel::ReadUncommitted => PgIsolationLevel::ReadUncommitted,
+                    IsolationLevel::ReadCommitted => PgIsolationLevel::ReadCommitted,
+                    IsolationLevel::RepeatableRead => PgIsolationLevel::RepeatableRead,
+                    IsolationLevel::Serializable => PgIsolationLevel::Serializable,
+                });
+            }
+
+            // Only set when asked for, so the session defaults still apply
+            if options.read_only {
+                builder = builder.read_only(true);
+            }
+
+            if options.deferrable {
+                builder = builder.deferrable(true);
+            }
+
+            Ok(Transaction(builder.start().await?))
         }
     }
 }
@@ -1,22 +1,133 @@
+use core::fmt;
+
 use deadpool_postgres::{CreatePoolError, PoolError};
+use tokio_postgres::error::SqlState;
+use usql_core::{DatabaseError, ErrorKind};
+use usql_value::Type;
 
 #[derive(Debug)]
-pub struct Error {}
+pub enum Error {
+    Postgres(tokio_postgres::Error),
+    Pool(PoolError),
+    CreatePool(CreatePoolError),
+    NotFound,
+    Convert { found: Option<Type>, expected: Type },
+}
+
+impl Error {
+    /// The SQLSTATE code reported by the server, if any
+    pub fn code(&self) -> Option<&tokio_postgres::error::SqlState> {
+        self.postgres()?.code()
+    }
+
+    fn postgres(&self) -> Option<&tokio_postgres::Error> {
+        match self {
+            Self::Postgres(err) | Self::Pool(PoolError::Backend(err)) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 impl From<CreatePoolError> for Error {
-    fn from(_value: CreatePoolError) -> Self {
-        Error {}
+    fn from(value: CreatePoolError) -> Self {
+        Error::CreatePool(value)
     }
 }
 
 impl From<PoolError> for Error {
-    fn from(_value: PoolError) -> Self {
-        todo!()
+    fn from(value: PoolError) -> Self {
+        match value {
+            PoolError::Backend(err) => Error::Postgres(err),
+            err => Error::Pool(err),
+        }
     }
 }
 
 impl From<tokio_postgres::Error> for Error {
-    fn from(_value: tokio_postgres::Error) -> Self {
-        todo!()
+    fn from(value: tokio_postgres::Error) -> Self {
+        Error::Postgres(value)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Postgres(err) => write!(f, "{}", err),
+            Self::Pool(err) => write!(f, "{}", err),
+            Self::CreatePool(err) => write!(f, "{}", err),
+            Self::NotFound => write!(f, "not found"),
+            Self::Convert { found, expected } => {
+                write!(
+                    f,
+                    "Convert: found: {}, expected {}",
+                    found
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| "null".to_string()),
+                    expected
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Postgres(err) => Some(err),
+            Self::Pool(err) => Some(err),
+            Self::CreatePool(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl DatabaseError for Error {
+    fn kind(&self) -> ErrorKind {
+        let Some(err) = self.postgres() else {
+            return ErrorKind::Other;
+        };
+
+        if err.is_closed() {
+            return ErrorKind::ConnectionLost;
+        }
+
+        let Some(code) = err.code() else {
+            return ErrorKind::Other;
+        };
+
+        if *code == SqlState::UNIQUE_VIOLATION {
+            ErrorKind::UniqueViolation
+        } else if *code == SqlState::FOREIGN_KEY_VIOLATION {
+            ErrorKind::ForeignKeyViolation
+        } else if *code == SqlState::NOT_NULL_VIOLATION {
+            ErrorKind::NotNullViolation
+        } else if *code == SqlState::CHECK_VIOLATION {
+            ErrorKind::CheckViolation
+        } else if *code == SqlState::T_R_SERIALIZATION_FAILURE
+            || *code == SqlState::T_R_DEADLOCK_DETECTED
+        {
+            ErrorKind::SerializationFailure
+        } else if *code == SqlState::LOCK_NOT_AVAILABLE {
+            ErrorKind::Busy
+        } else if *code == SqlState::SYNTAX_ERROR {
+            ErrorKind::Syntax
+        } else if code.code().starts_with("08")
+            || *code == SqlState::ADMIN_SHUTDOWN
+            || *code == SqlState::CRASH_SHUTDOWN
+        {
+            // Class 08 is connection exceptions
+            ErrorKind::ConnectionLost
+        } else {
+            ErrorKind::Other
+        }
+    }
+
+    fn constraint(&self) -> Option<&str> {
+        self.postgres()?.as_db_error()?.constraint()
+    }
+
+    fn table(&self) -> Option<&str> {
+        self.postgres()?.as_db_error()?.table()
     }
 }
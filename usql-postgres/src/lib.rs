@@ -6,3 +6,13 @@ mod pool;
 mod row;
 mod stmt;
 mod transaction;
+
+pub use self::{
+    conn::Conn as PostgresConn, connector::Info as PostgresDatabaseInfo, connector::Postgres,
+    error::Error as PostgresError, options::Options as PostgresOptions, options::Tls,
+    pool::Pool as PostgresPool, row::Row as PostgresRow, stmt::Statement as PostgresStatement,
+    transaction::Transaction as PostgresTransaction,
+};
+
+pub use deadpool_postgres::{Config, PoolConfig};
+pub use tokio_postgres::error::SqlState;
@@ -1,24 +1,38 @@
+use usql_core::{ColumnIndex, Connector};
+use usql_value::{Type, Value, ValueCow, ValueRef};
 
-use crate::connector::Postgres;
+use crate::{connector::Postgres, error::Error};
 
 pub struct Row(pub(crate) tokio_postgres::row::Row);
 
+impl Row {
+    fn index(&self, index: ColumnIndex<'_>) -> Option<usize> {
+        match index {
+            ColumnIndex::Index(idx) => (idx < self.0.len()).then_some(idx),
+            ColumnIndex::Named(name) => self.0.columns().iter().position(|m| m.name() == name),
+        }
+    }
+}
+
 impl usql_core::Row for Row {
     type Connector = Postgres;
 
     fn get<'a>(
         &'a self,
-        _index: usql_core::ColumnIndex<'_>,
-    ) -> Result<usql_value::ValueCow<'a>, <Self::Connector as usql_core::Connector>::Error> {
-        todo!()
+        index: ColumnIndex<'_>,
+    ) -> Result<ValueCow<'a>, <Self::Connector as Connector>::Error> {
+        let idx = self.index(index).ok_or(Error::NotFound)?;
+        let value: Value = self.0.try_get(idx)?;
+        Ok(ValueCow::Owned(value))
     }
 
     fn get_typed<'a>(
         &'a self,
-        _index: usql_core::ColumnIndex<'_>,
-        _ty: usql_value::Type,
-    ) -> Result<usql_value::ValueCow<'a>, <Self::Connector as usql_core::Connector>::Error> {
-        todo!()
+        index: ColumnIndex<'_>,
+        ty: Type,
+    ) -> Result<ValueCow<'a>, <Self::Connector as Connector>::Error> {
+        let value = usql_core::Row::get(self, index)?;
+        get_typed(value, ty)
     }
 
     fn len(&self) -> usize {
@@ -29,3 +43,55 @@ impl usql_core::Row for Row {
         self.0.columns().get(idx).map(|m| m.name())
     }
 }
+
+// Postgres values are already typed, so only widen/narrow numbers
+// and check the rest
+fn get_typed<'a>(value: ValueCow<'a>, ty: Type) -> Result<ValueCow<'a>, Error> {
+    let convert_err = |value: &ValueCow<'a>, expected: Type| Error::Convert {
+        found: value.as_ref().get_type(),
+        expected,
+    };
+
+    let value = match (&ty, value.as_ref()) {
+        (Type::Any, _) | (_, ValueRef::Null) => value,
+        (Type::SmallInt, ValueRef::SmallInt(_))
+        | (Type::Int, ValueRef::Int(_))
+        | (Type::BigInt, ValueRef::BigInt(_))
+        | (Type::Float, ValueRef::Float(_))
+        | (Type::Double, ValueRef::Double(_))
+        | (Type::Bool, ValueRef::Bool(_))
+        | (Type::Text, ValueRef::Text(_))
+        | (Type::Blob, ValueRef::ByteArray(_))
+        | (Type::Date, ValueRef::Date(_))
+        | (Type::Time, ValueRef::Time(_))
+        | (Type::DateTime, ValueRef::Timestamp(_))
+        | (Type::Uuid, ValueRef::Uuid(_))
+        | (Type::Json, ValueRef::Json(_))
+        | (Type::Array(_), ValueRef::Array(_)) => value,
+        (Type::SmallInt, ValueRef::Int(i)) => Value::SmallInt(
+            i.try_into()
+                .map_err(|_| convert_err(&value, Type::SmallInt))?,
+        )
+        .into(),
+        (Type::SmallInt, ValueRef::BigInt(i)) => Value::SmallInt(
+            i.try_into()
+                .map_err(|_| convert_err(&value, Type::SmallInt))?,
+        )
+        .into(),
+        (Type::Int, ValueRef::SmallInt(i)) => Value::Int(i as _).into(),
+        (Type::Int, ValueRef::BigInt(i)) => {
+            Value::Int(i.try_into().map_err(|_| convert_err(&value, Type::Int))?).into()
+        }
+        (Type::BigInt, ValueRef::SmallInt(i)) => Value::BigInt(i as _).into(),
+        (Type::BigInt, ValueRef::Int(i)) => Value::BigInt(i as _).into(),
+        (Type::Float, ValueRef::Double(f)) => Value::Float((*f as f32).into()).into(),
+        (Type::Double, ValueRef::Float(f)) => Value::Double((*f as f64).into()).into(),
+        (Type::Json, ValueRef::Text(text)) => {
+            let json = serde_json::from_str(text).map_err(|_| convert_err(&value, Type::Json))?;
+            Value::Json(json).into()
+        }
+        _ => return Err(convert_err(&value, ty)),
+    };
+
+    Ok(value)
+}
@@ -1,22 +1,43 @@
-use usql_core::Executor;
+use futures::{TryStreamExt, pin_mut};
+use usql_core::{Connector, Executor, QueryResult};
 
-use crate::connector::Postgres;
+use crate::{connector::Info, connector::Postgres, row::Row, stmt::Statement};
 
 pub struct Transaction<'conn>(pub(crate) deadpool_postgres::Transaction<'conn>);
 
 impl<'conn> usql_core::Transaction<'conn> for Transaction<'conn> {
+    type Savepoint<'a>
+        = Transaction<'a>
+    where
+        Self: 'a;
+
+    fn savepoint(
+        &mut self,
+    ) -> impl Future<
+        Output = Result<Self::Savepoint<'_>, <Self::Connector as usql_core::Connector>::Error>,
+    > + Send {
+        // Nested transactions are issued as `SAVEPOINT`s by tokio-postgres
+        async move { Ok(Transaction(self.0.transaction().await?)) }
+    }
+
     fn commit(
         self,
     ) -> impl Future<Output = Result<(), <Self::Connector as usql_core::Connector>::Error>> + Send
     {
-        async move { todo!() }
+        async move {
+            self.0.commit().await?;
+            Ok(())
+        }
     }
 
     fn rollback(
         self,
     ) -> impl Future<Output = Result<(), <Self::Connector as usql_core::Connector>::Error>> + Send
     {
-        async move { todo!() }
+        async move {
+            self.0.rollback().await?;
+            Ok(())
+        }
     }
 }
 
@@ -24,12 +45,12 @@ impl<'conn> Executor for Transaction<'conn> {
     type Connector = Postgres;
 
     fn db_info(&self) -> <Self::Connector as usql_core::Connector>::Info {
-        todo!()
+        Info
     }
 
     fn prepare<'a>(
         &'a self,
-        _query: &'a str,
+        query: &'a str,
     ) -> impl Future<
         Output = Result<
             <Self::Connector as usql_core::Connector>::Statement,
@@ -37,31 +58,51 @@ impl<'conn> Executor for Transaction<'conn> {
         >,
     > + Send
     + 'a {
-        async move { todo!() }
+        async move { Ok(Statement(self.0.prepare_cached(query).await?)) }
     }
 
     fn query<'a>(
         &'a self,
-        _stmt: &'a mut <Self::Connector as usql_core::Connector>::Statement,
-        _params: Vec<usql_value::ValueCow<'a>>,
+        stmt: &'a mut <Self::Connector as usql_core::Connector>::Statement,
+        params: Vec<usql_value::ValueCow<'a>>,
     ) -> usql_core::QueryStream<'a, Self::Connector> {
-        todo!()
+        let stream = async_stream::try_stream! {
+            let stream = self.0
+            .query_raw(&stmt.0, params.into_iter().map(|m| m.to_owned())).await?;
+
+            pin_mut!(stream);
+
+            while let Some(next) = stream.try_next().await? {
+                yield Row(next);
+            }
+        };
+
+        Box::pin(stream)
     }
 
     fn exec<'a>(
         &'a self,
-        _stmt: &'a mut <Self::Connector as usql_core::Connector>::Statement,
-        _params: Vec<usql_value::ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as usql_core::Connector>::Error>> + Send + 'a
+        stmt: &'a mut <Self::Connector as usql_core::Connector>::Statement,
+        params: Vec<usql_value::ValueCow<'a>>,
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
     {
-        async move { todo!() }
+        async move {
+            let affected_rows = self
+                .0
+                .execute_raw(&stmt.0, params.into_iter().map(|m| m.to_owned()))
+                .await?;
+            Ok(QueryResult::new(affected_rows, None))
+        }
     }
 
     fn exec_batch<'a>(
         &'a self,
-        _stmt: &'a str,
+        stmt: &'a str,
     ) -> impl Future<Output = Result<(), <Self::Connector as usql_core::Connector>::Error>> + Send + 'a
     {
-        async move { todo!() }
+        async move {
+            self.0.batch_execute(stmt).await?;
+            Ok(())
+        }
     }
 }
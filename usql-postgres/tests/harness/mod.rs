@@ -0,0 +1,149 @@
+use std::{
+    net::TcpListener,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use tokio_postgres::NoTls;
+
+/// A throwaway Postgres database for the integration tests.
+///
+/// Uses the server at `USQL_POSTGRES_URL` when set, each test then gets its
+/// own schema which is dropped again with the server. Otherwise runs
+/// `initdb`/`pg_ctl` from `PATH` against a temporary data directory.
+///
+/// Panics when neither is available, the tests using it are `#[ignore]`d
+/// and run with `cargo test -p usql-postgres -- --ignored`.
+pub struct TestServer {
+    pub url: String,
+    cleanup: Cleanup,
+}
+
+enum Cleanup {
+    Schema { url: String, schema: String },
+    Cluster { data_dir: PathBuf },
+}
+
+impl TestServer {
+    pub async fn start() -> TestServer {
+        match std::env::var("USQL_POSTGRES_URL") {
+            Ok(url) => TestServer::schema(url).await,
+            Err(_) => TestServer::cluster(),
+        }
+    }
+
+    async fn schema(url: String) -> TestServer {
+        let schema = format!("usql_test_{}", uuid());
+
+        execute(&url, &format!("CREATE SCHEMA \"{schema}\"")).await;
+
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let test_url = format!("{url}{separator}options=-c%20search_path%3D{schema}");
+
+        TestServer {
+            url: test_url,
+            cleanup: Cleanup::Schema { url, schema },
+        }
+    }
+
+    fn cluster() -> TestServer {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("free port")
+            .port();
+
+        let data_dir = std::env::temp_dir().join(format!("usql-postgres-{}", uuid()));
+
+        let initdb = Command::new("initdb")
+            .arg("-D")
+            .arg(&data_dir)
+            .args(["-U", "postgres", "--auth=trust"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+
+        assert!(
+            matches!(initdb, Ok(status) if status.success()),
+            "initdb not available, install postgres or set USQL_POSTGRES_URL"
+        );
+
+        let server = TestServer {
+            url: format!("postgres://postgres@127.0.0.1:{port}/postgres"),
+            cleanup: Cleanup::Cluster {
+                data_dir: data_dir.clone(),
+            },
+        };
+
+        let started = Command::new("pg_ctl")
+            .arg("-D")
+            .arg(&data_dir)
+            .arg("-o")
+            .arg(format!(
+                "-p {port} -k {} -c listen_addresses=127.0.0.1",
+                data_dir.display()
+            ))
+            .args(["-w", "start"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .expect("pg_ctl");
+
+        assert!(started.success(), "could not start postgres");
+
+        server
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        match &self.cleanup {
+            Cleanup::Schema { url, schema } => {
+                let url = url.clone();
+                let stmt = format!("DROP SCHEMA IF EXISTS \"{schema}\" CASCADE");
+
+                // Drop runs inside the test's runtime, which can't be blocked on
+                std::thread::spawn(move || {
+                    tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .expect("runtime")
+                        .block_on(execute(&url, &stmt))
+                })
+                .join()
+                .ok();
+            }
+            Cleanup::Cluster { data_dir } => {
+                Command::new("pg_ctl")
+                    .arg("-D")
+                    .arg(data_dir)
+                    .args(["-m", "immediate", "stop"])
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status()
+                    .ok();
+
+                std::fs::remove_dir_all(data_dir).ok();
+            }
+        }
+    }
+}
+
+async fn execute(url: &str, stmt: &str) {
+    let (client, conn) = tokio_postgres::connect(url, NoTls)
+        .await
+        .expect("connect to USQL_POSTGRES_URL");
+    let conn = tokio::spawn(conn);
+
+    client.batch_execute(stmt).await.expect(stmt);
+
+    drop(client);
+    conn.await.ok();
+}
+
+fn uuid() -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    format!("{}_{}", std::process::id(), nanos)
+}
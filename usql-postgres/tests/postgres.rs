@@ -0,0 +1,279 @@
+mod harness;
+
+use futures::TryStreamExt;
+use usql_core::{
+    ColumnIndex, Connection, Connector, DatabaseError, ErrorKind, Executor, IsolationLevel, Pool,
+    Row, Transaction, TransactionOptions,
+};
+use usql_postgres::{Postgres, PostgresError, PostgresOptions, SqlState};
+use usql_value::{Type, Value};
+
+use harness::TestServer;
+
+async fn connect(server: &TestServer) -> <Postgres as Connector>::Connection {
+    let pool = Postgres::create_pool(PostgresOptions::url(&server.url))
+        .await
+        .expect("pool");
+    pool.get().await.expect("conn")
+}
+
+async fn count<E: Executor<Connector = Postgres>>(exec: &E) -> i64 {
+    let mut stmt = exec.prepare("SELECT COUNT(*) FROM users").await.unwrap();
+    let rows = exec
+        .query(&mut stmt, vec![])
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    match rows[0].get(ColumnIndex::Index(0)).unwrap().to_owned() {
+        Value::BigInt(n) => n,
+        v => panic!("unexpected value: {v:?}"),
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs postgres, initdb on PATH or USQL_POSTGRES_URL"]
+async fn roundtrip() {
+    let server = TestServer::start().await;
+    let conn = connect(&server).await;
+
+    conn.exec_batch(
+        "CREATE TABLE users (id SERIAL PRIMARY KEY, name TEXT NOT NULL, age INT8, active BOOL)",
+    )
+    .await
+    .unwrap();
+
+    let mut stmt = conn
+        .prepare("INSERT INTO users (name, age, active) VALUES ($1, $2, $3)")
+        .await
+        .unwrap();
+
+    let ret = conn
+        .exec(
+            &mut stmt,
+            vec![
+                Value::from("Rasmus").into(),
+                Value::Int(42).into(),
+                Value::Bool(true).into(),
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(ret.affected_rows, 1);
+
+    let mut stmt = conn
+        .prepare("SELECT id, name, age, active FROM users")
+        .await
+        .unwrap();
+
+    let rows = conn
+        .query(&mut stmt, vec![])
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+
+    assert_eq!(row.len(), 4);
+    assert_eq!(row.column_name(1), Some("name"));
+    assert_eq!(
+        row.get(ColumnIndex::Named("id")).unwrap().to_owned(),
+        Value::Int(1)
+    );
+    assert_eq!(
+        row.get(ColumnIndex::Named("name")).unwrap().to_owned(),
+        Value::from("Rasmus")
+    );
+    assert_eq!(
+        row.get_typed(ColumnIndex::Named("id"), Type::BigInt)
+            .unwrap()
+            .to_owned(),
+        Value::BigInt(1)
+    );
+    assert_eq!(
+        row.get(ColumnIndex::Index(3)).unwrap().to_owned(),
+        Value::Bool(true)
+    );
+    assert!(matches!(
+        row.get(ColumnIndex::Named("missing")),
+        Err(PostgresError::NotFound)
+    ));
+}
+
+#[tokio::test]
+#[ignore = "needs postgres, initdb on PATH or USQL_POSTGRES_URL"]
+async fn transactions() {
+    let server = TestServer::start().await;
+    let mut conn = connect(&server).await;
+
+    conn.exec_batch("CREATE TABLE users (id SERIAL PRIMARY KEY, name TEXT NOT NULL)")
+        .await
+        .unwrap();
+
+    let trans = conn.begin().await.unwrap();
+    trans
+        .exec_batch("INSERT INTO users (name) VALUES ('rollback')")
+        .await
+        .unwrap();
+    assert_eq!(count(&trans).await, 1);
+    trans.rollback().await.unwrap();
+
+    assert_eq!(count(&conn).await, 0);
+
+    let trans = conn.begin().await.unwrap();
+    trans
+        .exec_batch("INSERT INTO users (name) VALUES ('commit')")
+        .await
+        .unwrap();
+    trans.commit().await.unwrap();
+
+    assert_eq!(count(&conn).await, 1);
+}
+
+#[tokio::test]
+#[ignore = "needs postgres, initdb on PATH or USQL_POSTGRES_URL"]
+async fn savepoints() {
+    let server = TestServer::start().await;
+    let mut conn = connect(&server).await;
+
+    conn.exec_batch("CREATE TABLE users (id SERIAL PRIMARY KEY, name TEXT NOT NULL)")
+        .await
+        .unwrap();
+
+    let mut trans = conn.begin().await.unwrap();
+    trans
+        .exec_batch("INSERT INTO users (name) VALUES ('outer')")
+        .await
+        .unwrap();
+
+    let savepoint = trans.savepoint().await.unwrap();
+    savepoint
+        .exec_batch("INSERT INTO users (name) VALUES ('rollback')")
+        .await
+        .unwrap();
+    assert_eq!(count(&savepoint).await, 2);
+    savepoint.rollback().await.unwrap();
+
+    assert_eq!(count(&trans).await, 1);
+
+    let mut savepoint = trans.savepoint().await.unwrap();
+    savepoint
+        .exec_batch("INSERT INTO users (name) VALUES ('release')")
+        .await
+        .unwrap();
+    let nested = savepoint.savepoint().await.unwrap();
+    nested
+        .exec_batch("INSERT INTO users (name) VALUES ('nested')")
+        .await
+        .unwrap();
+    nested.commit().await.unwrap();
+    savepoint.commit().await.unwrap();
+
+    trans.commit().await.unwrap();
+
+    assert_eq!(count(&conn).await, 3);
+}
+
+#[tokio::test]
+#[ignore = "needs postgres, initdb on PATH or USQL_POSTGRES_URL"]
+async fn transaction_options() {
+    let server = TestServer::start().await;
+    let mut conn = connect(&server).await;
+
+    conn.exec_batch("CREATE TABLE users (id SERIAL PRIMARY KEY, name TEXT NOT NULL)")
+        .await
+        .unwrap();
+
+    let options = TransactionOptions::new()
+        .isolation_level(IsolationLevel::Serializable)
+        .read_only(true)
+        .deferrable(true);
+
+    let trans = conn.begin_with(options).await.unwrap();
+    let mut stmt = trans.prepare("SHOW transaction_isolation").await.unwrap();
+    let rows = trans
+        .query(&mut stmt, vec![])
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        rows[0].get(ColumnIndex::Index(0)).unwrap().to_owned(),
+        Value::Text("serializable".into())
+    );
+
+    let err = trans
+        .exec_batch("INSERT INTO users (name) VALUES ('read only')")
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::READ_ONLY_SQL_TRANSACTION));
+}
+
+#[tokio::test]
+#[ignore = "needs postgres, initdb on PATH or USQL_POSTGRES_URL"]
+async fn errors() {
+    let server = TestServer::start().await;
+    let conn = connect(&server).await;
+
+    conn.exec_batch("CREATE TABLE users (id SERIAL PRIMARY KEY, name TEXT UNIQUE)")
+        .await
+        .unwrap();
+
+    conn.exec_batch("INSERT INTO users (name) VALUES ('dup')")
+        .await
+        .unwrap();
+
+    let err = conn
+        .exec_batch("INSERT INTO users (name) VALUES ('dup')")
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.code(), Some(&SqlState::UNIQUE_VIOLATION));
+    assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+    assert_eq!(err.constraint(), Some("users_name_key"));
+    assert_eq!(err.table(), Some("users"));
+
+    let err = conn.exec_batch("SELEC 1").await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Syntax);
+}
+
+#[tokio::test]
+#[ignore = "needs postgres, initdb on PATH or USQL_POSTGRES_URL"]
+async fn timestamptz() {
+    let server = TestServer::start().await;
+    let conn = connect(&server).await;
+
+    conn.exec_batch("CREATE TABLE events (id SERIAL PRIMARY KEY, at TIMESTAMPTZ NOT NULL)")
+        .await
+        .unwrap();
+
+    let at = chrono::NaiveDate::from_ymd_opt(2024, 3, 1)
+        .and_then(|date| date.and_hms_micro_opt(12, 30, 0, 5))
+        .unwrap();
+
+    let mut stmt = conn
+        .prepare("INSERT INTO events (at) VALUES ($1)")
+        .await
+        .unwrap();
+    conn.exec(&mut stmt, vec![Value::Timestamp(at).into()])
+        .await
+        .unwrap();
+
+    // Read back as UTC, whatever the session's time zone
+    conn.exec_batch("SET TIME ZONE 'America/New_York'")
+        .await
+        .unwrap();
+
+    let mut stmt = conn.prepare("SELECT at FROM events").await.unwrap();
+    let rows = conn
+        .query(&mut stmt, vec![])
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        rows[0].get(ColumnIndex::Named("at")).unwrap().to_owned(),
+        Value::Timestamp(at)
+    );
+}
@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use klaver_util::{StringRef, throw, throw_if};
 use rquickjs::{Ctx, FromJs, JsLifetime, Object, class::Trace};
-use usql_any::{AnyPool, LibSqlConfig, SqliteConfig};
+use usql_any::{AnyPool, LibSqlConfig, MysqlConfig, PostgresConfig, SqliteConfig};
 use usql_core::Pool;
 
 use crate::JsConn;
@@ -65,16 +65,40 @@ impl<'js> FromJs<'js> for Config {
                 usql_any::Config {
                     workers: None,
                     kind: usql_any::DatabaseConfig::LibSql(
-                        path.map(|m| LibSqlConfig::Path(PathBuf::from(m)))
-                            .unwrap_or(LibSqlConfig::Memory),
+                        path.map(|m| LibSqlConfig::Path {
+                            path: PathBuf::from(m),
+                        })
+                        .unwrap_or(LibSqlConfig::Memory),
                     ),
                 }
             }
+            "postgres" => usql_any::Config {
+                workers: None,
+                kind: usql_any::DatabaseConfig::Postgres(PostgresConfig {
+                    host: obj.get("host")?,
+                    port: port(&obj)?,
+                    user: obj.get("user")?,
+                    password: obj.get("password")?,
+                    db: obj.get("db")?,
+                    pool_size: obj.get::<_, Option<u32>>("poolSize")?.map(|m| m as usize),
+                }),
+            },
+            "mysql" => usql_any::Config {
+                workers: None,
+                kind: usql_any::DatabaseConfig::Mysql(MysqlConfig {
+                    host: obj.get("host")?,
+                    port: port(&obj)?,
+                    user: obj.get("user")?,
+                    password: obj.get("password")?,
+                    db: obj.get("db")?,
+                    pool_size: obj.get::<_, Option<u32>>("poolSize")?.map(|m| m as usize),
+                }),
+            },
             _ => {
                 return Err(rquickjs::Error::new_from_js_message(
                     "string",
                     "database type",
-                    "Sqlite, libsql or postgres",
+                    "Sqlite, libsql, postgres or mysql",
                 ));
             }
         };
@@ -82,3 +106,13 @@ impl<'js> FromJs<'js> for Config {
         Ok(Config(cfg))
     }
 }
+
+fn port(obj: &Object<'_>) -> rquickjs::Result<Option<u16>> {
+    obj.get::<_, Option<u32>>("port")?
+        .map(|port| {
+            u16::try_from(port).map_err(|_| {
+                rquickjs::Error::new_from_js_message("number", "port", "Port out of range")
+            })
+        })
+        .transpose()
+}
@@ -81,4 +81,22 @@ export class Pool {
  */
 export type Config =
   | { type: "sqlite"; path?: string }
-  | { type: "libsql"; path?: string };
+  | { type: "libsql"; path?: string }
+  | {
+      type: "postgres";
+      host?: string;
+      port?: number;
+      user?: string;
+      password?: string;
+      db?: string;
+      poolSize?: number;
+    }
+  | {
+      type: "mysql";
+      host?: string;
+      port?: number;
+      user?: string;
+      password?: string;
+      db?: string;
+      poolSize?: number;
+    };
@@ -0,0 +1,107 @@
+#![no_std]
+
+/// The rowid SQLite reports for a statement run with `sql`.
+///
+/// `last_insert_rowid` is connection wide and only changed by a successful
+/// INSERT (or REPLACE), so it belongs to the statement just run only when that
+/// statement inserts and changed rows. Anything else gets `None`, even when a
+/// trigger inserted into another table.
+pub fn last_insert_id(sql: &str, affected_rows: u64, rowid: i64) -> Option<i64> {
+    (affected_rows > 0 && inserts(sql)).then_some(rowid)
+}
+
+// Looks at the statement's leading keyword, skipping comments and a WITH clause
+fn inserts(sql: &str) -> bool {
+    let bytes = sql.as_bytes();
+    let mut depth = 0usize;
+    let mut with = false;
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            quote @ (b'\'' | b'"' | b'`') => idx = skip_past(bytes, idx + 1, &[quote]),
+            b'[' => idx = skip_past(bytes, idx + 1, b"]"),
+            b'-' if bytes.get(idx + 1) == Some(&b'-') => idx = skip_past(bytes, idx + 2, b"\n"),
+            b'/' if bytes.get(idx + 1) == Some(&b'*') => idx = skip_past(bytes, idx + 2, b"*/"),
+            b if b.is_ascii_alphabetic() || b == b'_' => {
+                let start = idx;
+                while idx < bytes.len()
+                    && (bytes[idx].is_ascii_alphanumeric() || bytes[idx] == b'_')
+                {
+                    idx += 1;
+                }
+                let word = &sql[start..idx];
+
+                if depth == 0 {
+                    if !with && word.eq_ignore_ascii_case("with") {
+                        with = true;
+                        continue;
+                    }
+
+                    let verb = ["insert", "replace", "select", "update", "delete", "values"]
+                        .iter()
+                        .find(|verb| word.eq_ignore_ascii_case(verb));
+
+                    match verb {
+                        Some(verb) => return matches!(*verb, "insert" | "replace"),
+                        // Names, AS and RECURSIVE of the common table expressions
+                        None if with => {}
+                        None => return false,
+                    }
+                }
+                continue;
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+
+    false
+}
+
+// The index of the last byte of the first `end` found from `from`
+fn skip_past(bytes: &[u8], from: usize, end: &[u8]) -> usize {
+    bytes[from.min(bytes.len())..]
+        .windows(end.len())
+        .position(|window| window == end)
+        .map(|pos| from + pos + end.len() - 1)
+        .unwrap_or(bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::last_insert_id;
+
+    #[test]
+    fn test_last_insert_id() {
+        let cases = [
+            ("INSERT INTO users (name) VALUES (?)", 1, Some(7)),
+            ("insert or replace into users (name) values (?)", 1, Some(7)),
+            ("REPLACE INTO users (id, name) VALUES (?, ?)", 1, Some(7)),
+            (
+                "  -- add a user\n/* comment */ INSERT INTO users DEFAULT VALUES",
+                1,
+                Some(7),
+            ),
+            (
+                "WITH \"new\"(name) AS (SELECT 'a') INSERT INTO users (name) SELECT name FROM \"new\"",
+                1,
+                Some(7),
+            ),
+            ("INSERT OR IGNORE INTO users (name) VALUES (?)", 0, None),
+            ("UPDATE users SET name = 'insert' WHERE id = ?", 1, None),
+            ("DELETE FROM users WHERE id = ?", 3, None),
+            (
+                "WITH old AS (SELECT 1) DELETE FROM users WHERE id IN old",
+                1,
+                None,
+            ),
+        ];
+
+        for (sql, affected_rows, expected) in cases {
+            assert_eq!(last_insert_id(sql, affected_rows, 7), expected, "{sql}");
+        }
+    }
+}
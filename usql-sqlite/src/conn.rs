@@ -2,11 +2,10 @@ use super::{
     SqliteDatabaseInfo, SqliteStatement,
     connector::Sqlite,
     error::Error,
-    query_result::QueryResult,
     row::Row,
     traits::Params,
     transaction::Transaction,
-    worker::{Request, open_worker},
+    worker::{Query, Request, open_worker},
 };
 #[cfg(feature = "geometry")]
 use ::rusqlite::LoadExtensionGuard;
@@ -22,7 +21,7 @@ use std::{
     string::ToString,
     task::Poll,
 };
-use usql_core::{Connection, Connector, Executor};
+use usql_core::{Connection, Connector, Executor, QueryResult, TransactionOptions};
 use usql_value::ValueCow;
 
 pub struct Conn {
@@ -56,11 +55,20 @@ impl Conn {
     }
 
     pub async fn begin_transaction(&mut self) -> Result<Transaction<'_>, Error> {
+        self.begin_transaction_with(TransactionOptions::default())
+            .await
+    }
+
+    pub async fn begin_transaction_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> Result<Transaction<'_>, Error> {
         let (sx, rx) = flume::bounded(1);
         let (ready_sx, ready_rx) = oneshot::channel();
 
         self.channel
             .send_async(Request::Begin {
+                options,
                 channel: rx,
                 ready: ready_sx,
             })
@@ -80,8 +88,11 @@ impl Conn {
         sql: impl ToString,
         values: P,
     ) -> Result<QueryStream, Error> {
+        self.fetch(Query::Sql(sql.to_string()), values).await
+    }
+
+    async fn fetch<P: Params>(&self, stmt: Query, values: P) -> Result<QueryStream, Error> {
         let values = values.into_params();
-        let stmt = sql.to_string();
         let (returns, rx) = flume::bounded(1);
 
         self.channel
@@ -108,15 +119,35 @@ impl Conn {
         Ok(first)
     }
 
+    pub async fn prepare(&self, sql: impl ToString) -> Result<SqliteStatement, Error> {
+        let sql = sql.to_string();
+        let (sx, rx) = oneshot::channel();
+        self.channel
+            .send_async(Request::Prepare {
+                stmt: sql.clone(),
+                returns: sx,
+            })
+            .await
+            .map_err(|_| Error::Channel)?;
+
+        let prepared = rx.await.map_err(|_| Error::Channel)??;
+
+        Ok(SqliteStatement::new(sql, prepared))
+    }
+
     pub async fn exec<P: Params>(
         &self,
         sql: impl ToString,
         values: P,
     ) -> Result<QueryResult, Error> {
+        self.execute(Query::Sql(sql.to_string()), values).await
+    }
+
+    async fn execute<P: Params>(&self, stmt: Query, values: P) -> Result<QueryResult, Error> {
         let (sx, rx) = oneshot::channel();
         self.channel
             .send_async(Request::Exec {
-                stmt: sql.to_string(),
+                stmt,
                 values: values.into_params(),
                 returns: sx,
             })
@@ -193,11 +224,12 @@ impl Stream for QueryStream {
 impl Connection for Conn {
     type Transaction<'conn> = Transaction<'conn>;
 
-    fn begin(
+    fn begin_with(
         &mut self,
+        options: TransactionOptions,
     ) -> impl Future<Output = Result<Self::Transaction<'_>, <Self::Connector as Connector>::Error>> + Send
     {
-        async move { self.begin_transaction().await }
+        async move { self.begin_transaction_with(options).await }
     }
 }
 
@@ -218,11 +250,7 @@ impl Executor for Conn {
         >,
     > + Send
     + 'a {
-        async move {
-            Ok(SqliteStatement {
-                sql: query.to_string(),
-            })
-        }
+        async move { self.prepare(query).await }
     }
 
     fn query<'a>(
@@ -231,7 +259,7 @@ impl Executor for Conn {
         params: std::vec::Vec<ValueCow<'a>>,
     ) -> usql_core::QueryStream<'a, Self::Connector> {
         let stream = async_stream::try_stream! {
-            let mut stream = self.query(&stmt.sql, params).await?;
+            let mut stream = self.fetch(stmt.query(), params).await?;
 
             while let Some(next) = usql_core::util::next(&mut stream).await.transpose()? {
                 yield next
@@ -245,11 +273,9 @@ impl Executor for Conn {
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: std::vec::Vec<ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
-        async move {
-            self.exec(&stmt.sql, params).await?;
-            Ok(())
-        }
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
+        async move { self.execute(stmt.query(), params).await }
     }
 
     fn exec_batch<'a>(
@@ -1,5 +1,6 @@
 use core::fmt;
 
+use usql_core::{DatabaseError, ErrorKind};
 use usql_value::Type;
 
 #[derive(Debug)]
@@ -48,6 +49,78 @@ impl core::error::Error for Error {
     }
 }
 
+impl Error {
+    fn failure(&self) -> Option<(i32, Option<&str>)> {
+        match self {
+            Self::Sqlite(rusqlite::Error::SqliteFailure(err, msg)) => {
+                Some((err.extended_code, msg.as_deref()))
+            }
+            _ => None,
+        }
+    }
+
+    // Constraint messages look like `UNIQUE constraint failed: users.email`
+    fn constraint_detail(&self) -> Option<&str> {
+        let (_, msg) = self.failure()?;
+        msg?.split_once("constraint failed: ")
+            .map(|(_, detail)| detail)
+    }
+}
+
+impl DatabaseError for Error {
+    fn kind(&self) -> ErrorKind {
+        if let Self::Channel = self {
+            return ErrorKind::ConnectionLost;
+        }
+
+        let Some((code, msg)) = self.failure() else {
+            return match self {
+                Self::Sqlite(err) => match err.sqlite_error_code() {
+                    Some(
+                        rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked,
+                    ) => ErrorKind::Busy,
+                    _ => ErrorKind::Other,
+                },
+                _ => ErrorKind::Other,
+            };
+        };
+
+        match code {
+            // SQLITE_CONSTRAINT_PRIMARYKEY and SQLITE_CONSTRAINT_UNIQUE
+            1555 | 2067 => ErrorKind::UniqueViolation,
+            // SQLITE_CONSTRAINT_FOREIGNKEY
+            787 => ErrorKind::ForeignKeyViolation,
+            // SQLITE_CONSTRAINT_NOTNULL
+            1299 => ErrorKind::NotNullViolation,
+            // SQLITE_CONSTRAINT_CHECK
+            275 => ErrorKind::CheckViolation,
+            // SQLITE_BUSY and SQLITE_LOCKED
+            code if matches!(code & 0xff, 5 | 6) => ErrorKind::Busy,
+            // SQLITE_ERROR
+            1 if msg.is_some_and(|msg| msg.contains("syntax error")) => ErrorKind::Syntax,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    fn constraint(&self) -> Option<&str> {
+        // Only check constraints are reported by name
+        match self.kind() {
+            ErrorKind::CheckViolation => self.constraint_detail(),
+            _ => None,
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        match self.kind() {
+            ErrorKind::UniqueViolation | ErrorKind::NotNullViolation => self
+                .constraint_detail()?
+                .split_once('.')
+                .map(|(table, _)| table),
+            _ => None,
+        }
+    }
+}
+
 impl From<deadpool::managed::PoolError<Error>> for Error {
     fn from(value: deadpool::managed::PoolError<Error>) -> Self {
         match value {
@@ -2,7 +2,6 @@ mod conn;
 mod connector;
 mod error;
 mod pool;
-mod query_result;
 mod row;
 mod statement;
 mod traits;
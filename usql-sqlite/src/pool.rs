@@ -13,7 +13,7 @@ use super::{
     error::Error,
     transaction::Transaction,
 };
-use usql_core::{Connection, Connector, Executor};
+use usql_core::{Connection, Connector, Executor, QueryResult, TransactionOptions};
 use usql_value::ValueCow;
 
 // pub type PooledConn = deadpool::managed::Object<Manager>;
@@ -44,6 +44,7 @@ pub struct ManagerOptions {
     path: Option<PathBuf>,
     flags: rusqlite::OpenFlags,
     setup: Option<Box<dyn SetupFunction + Send + Sync>>,
+    statement_cache_capacity: Option<usize>,
 }
 
 impl ManagerOptions {
@@ -64,6 +65,13 @@ impl ManagerOptions {
         self.setup = Some(Box::new(func));
         self
     }
+
+    /// Number of ad-hoc statements each connection keeps compiled, statements
+    /// from `prepare` are kept until they are finalized
+    pub fn statement_cache_capacity(mut self, capacity: usize) -> Self {
+        self.statement_cache_capacity = Some(capacity);
+        self
+    }
 }
 
 pub struct Manager {
@@ -71,6 +79,7 @@ pub struct Manager {
     flags: rusqlite::OpenFlags,
     _persist: Mutex<Option<Conn>>,
     setup: Option<Box<dyn SetupFunction + Send + Sync>>,
+    statement_cache_capacity: Option<usize>,
 }
 
 impl Manager {
@@ -79,6 +88,7 @@ impl Manager {
             path: Some(path.as_ref().to_path_buf()),
             flags: rusqlite::OpenFlags::default(),
             setup: None,
+            statement_cache_capacity: None,
         })
     }
 
@@ -87,6 +97,7 @@ impl Manager {
             path: None,
             flags: rusqlite::OpenFlags::default(),
             setup: None,
+            statement_cache_capacity: None,
         })
     }
 
@@ -100,6 +111,7 @@ impl Manager {
             source,
             flags: options.flags,
             setup: options.setup,
+            statement_cache_capacity: options.statement_cache_capacity,
             _persist: Mutex::new(None),
         }
     }
@@ -129,6 +141,14 @@ impl deadpool::managed::Manager for Manager {
                 }
             }?;
 
+            if let Some(capacity) = self.statement_cache_capacity {
+                conn.with(move |conn| {
+                    conn.set_prepared_statement_cache_capacity(capacity);
+                    Ok(())
+                })
+                .await?;
+            }
+
             if let Some(setup) = &self.setup {
                 let setup = setup.clone();
                 conn.with(move |conn| setup.call(conn)).await?;
@@ -182,11 +202,12 @@ pub struct PooledConn(deadpool::managed::Object<Manager>);
 impl Connection for PooledConn {
     type Transaction<'conn> = Transaction<'conn>;
 
-    fn begin(
+    fn begin_with(
         &mut self,
+        options: TransactionOptions,
     ) -> impl Future<Output = Result<Self::Transaction<'_>, <Self::Connector as Connector>::Error>> + Send
     {
-        async move { <Conn as Connection>::begin(self.0.as_mut()).await }
+        async move { <Conn as Connection>::begin_with(self.0.as_mut(), options).await }
     }
 }
 
@@ -222,7 +243,8 @@ impl Executor for PooledConn {
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: std::vec::Vec<ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
         <Conn as Executor>::exec(self.0.as_ref(), stmt, params)
     }
 
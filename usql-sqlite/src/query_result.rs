@@ -1,4 +0,0 @@
-pub struct QueryResult {
-    pub affected_rows: usize,
-    pub last_insert_id: i64,
-}
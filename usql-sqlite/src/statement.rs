@@ -1,15 +1,49 @@
 use std::string::String;
 
-use super::{connector::Sqlite, error::Error};
+use super::{
+    connector::Sqlite,
+    error::Error,
+    worker::{Prepared, Query},
+};
 
+/// A statement compiled once by the connection's worker and kept until it is
+/// finalized or dropped.
 pub struct Statement {
+    pub(super) id: u64,
     pub(super) sql: String,
+    pub(super) finalize: flume::Sender<u64>,
+}
+
+impl Statement {
+    pub(super) fn new(sql: String, prepared: Prepared) -> Statement {
+        Statement {
+            id: prepared.id,
+            sql,
+            finalize: prepared.finalize,
+        }
+    }
+
+    pub(super) fn query(&self) -> Query {
+        Query::Prepared {
+            id: self.id,
+            sql: self.sql.clone(),
+        }
+    }
+}
+
+impl Drop for Statement {
+    fn drop(&mut self) {
+        // The channel is unbounded so this never blocks, it is only closed
+        // when the worker is gone and the statement went with it
+        self.finalize.send(self.id).ok();
+    }
 }
 
 impl usql_core::Statement for Statement {
     type Connector = Sqlite;
 
     fn finalize(self) -> Result<(), Error> {
+        // Dropping it hands the statement back to the worker
         Ok(())
     }
 }
@@ -5,9 +5,12 @@ use std::string::ToString;
 use futures_channel::oneshot;
 
 use super::{Sqlite, SqliteDatabaseInfo, SqliteStatement};
-use super::{conn::QueryStream, query_result::QueryResult, row::Row, traits::Params};
-use super::{error::Error, worker::TransRequest};
-use usql_core::{Connector, Executor};
+use super::{conn::QueryStream, row::Row, traits::Params};
+use super::{
+    error::Error,
+    worker::{Query, TransRequest},
+};
+use usql_core::{Connector, Executor, QueryResult};
 use usql_value::ValueCow;
 
 pub struct Transaction<'conn> {
@@ -36,13 +39,33 @@ impl Transaction<'_> {
         wait.await.expect("wait")
     }
 
+    /// Start a nested transaction, backed by a savepoint, inside this one.
+    pub async fn savepoint(&mut self) -> Result<Transaction<'_>, Error> {
+        let (sx, rx) = flume::bounded(1);
+        let (ready, wait) = oneshot::channel();
+        self.channel
+            .send_async(TransRequest::Savepoint { channel: rx, ready })
+            .await
+            .map_err(|_| Error::Channel)?;
+
+        wait.await.map_err(|_| Error::Channel)??;
+
+        Ok(Transaction {
+            invariant: PhantomData,
+            channel: sx,
+        })
+    }
+
     pub async fn query<P: Params>(
         &self,
         sql: impl ToString,
         values: P,
     ) -> Result<QueryStream, Error> {
+        self.fetch(Query::Sql(sql.to_string()), values).await
+    }
+
+    async fn fetch<P: Params>(&self, stmt: Query, values: P) -> Result<QueryStream, Error> {
         let values = values.into_params();
-        let stmt = sql.to_string();
         let (returns, rx) = flume::bounded(1);
 
         self.channel
@@ -69,15 +92,35 @@ impl Transaction<'_> {
         first
     }
 
+    pub async fn prepare(&self, sql: impl ToString) -> Result<SqliteStatement, Error> {
+        let sql = sql.to_string();
+        let (sx, rx) = oneshot::channel();
+        self.channel
+            .send_async(TransRequest::Prepare {
+                stmt: sql.clone(),
+                returns: sx,
+            })
+            .await
+            .map_err(|_| Error::Channel)?;
+
+        let prepared = rx.await.map_err(|_| Error::Channel)??;
+
+        Ok(SqliteStatement::new(sql, prepared))
+    }
+
     pub async fn exec<P: Params>(
         &self,
         sql: impl ToString,
         values: P,
     ) -> Result<QueryResult, Error> {
+        self.execute(Query::Sql(sql.to_string()), values).await
+    }
+
+    async fn execute<P: Params>(&self, stmt: Query, values: P) -> Result<QueryResult, Error> {
         let (sx, rx) = oneshot::channel();
         self.channel
             .send_async(TransRequest::Exec {
-                stmt: sql.to_string(),
+                stmt,
                 values: values.into_params(),
                 returns: sx,
             })
@@ -109,6 +152,18 @@ impl Drop for Transaction<'_> {
 }
 
 impl<'conn> usql_core::Transaction<'conn> for Transaction<'conn> {
+    type Savepoint<'a>
+        = Transaction<'a>
+    where
+        Self: 'a;
+
+    fn savepoint(
+        &mut self,
+    ) -> impl Future<Output = Result<Self::Savepoint<'_>, <Self::Connector as Connector>::Error>> + Send
+    {
+        async move { self.savepoint().await }
+    }
+
     fn commit(
         self,
     ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send {
@@ -139,11 +194,7 @@ impl Executor for Transaction<'_> {
         >,
     > + Send
     + 'a {
-        async move {
-            Ok(SqliteStatement {
-                sql: query.to_string(),
-            })
-        }
+        async move { self.prepare(query).await }
     }
 
     fn query<'a>(
@@ -152,7 +203,7 @@ impl Executor for Transaction<'_> {
         params: std::vec::Vec<ValueCow<'a>>,
     ) -> usql_core::QueryStream<'a, Self::Connector> {
         let stream = async_stream::try_stream! {
-            let mut stream = self.query(&stmt.sql, params).await?;
+            let mut stream = self.fetch(stmt.query(), params).await?;
 
             while let Some(next) = usql_core::util::next(&mut stream).await.transpose()? {
                 yield next
@@ -166,11 +217,9 @@ impl Executor for Transaction<'_> {
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: std::vec::Vec<ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
-        async move {
-            self.exec(&stmt.sql, params).await?;
-            Ok(())
-        }
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
+        async move { self.execute(stmt.query(), params).await }
     }
 
     fn exec_batch<'a>(
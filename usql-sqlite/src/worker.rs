@@ -4,18 +4,28 @@ use std::{
     collections::HashMap,
     path::PathBuf,
     string::{String, ToString},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
     vec::Vec,
 };
 
 use futures_channel::oneshot;
 use rusqlite::types::Value;
 
-use super::{error::Error, query_result::QueryResult, row::Row};
+use usql_core::{QueryResult, TransactionBehavior, TransactionOptions};
+use usql_sqlite_util::last_insert_id;
+
+use super::{error::Error, row::Row};
 
 pub enum Request {
-    Exec {
+    Prepare {
         stmt: String,
+        returns: oneshot::Sender<Result<Prepared, rusqlite::Error>>,
+    },
+    Exec {
+        stmt: Query,
         values: Vec<rusqlite::types::Value>,
         returns: oneshot::Sender<Result<QueryResult, rusqlite::Error>>,
     },
@@ -24,11 +34,12 @@ pub enum Request {
         returns: oneshot::Sender<Result<(), rusqlite::Error>>,
     },
     Fetch {
-        stmt: String,
+        stmt: Query,
         values: Vec<rusqlite::types::Value>,
         returns: flume::Sender<Result<Row, rusqlite::Error>>,
     },
     Begin {
+        options: TransactionOptions,
         channel: flume::Receiver<TransRequest>,
         ready: oneshot::Sender<Result<(), rusqlite::Error>>,
     },
@@ -43,8 +54,12 @@ pub enum Request {
 }
 
 pub enum TransRequest {
-    Exec {
+    Prepare {
         stmt: String,
+        returns: oneshot::Sender<Result<Prepared, rusqlite::Error>>,
+    },
+    Exec {
+        stmt: Query,
         values: Vec<rusqlite::types::Value>,
         returns: oneshot::Sender<Result<QueryResult, rusqlite::Error>>,
     },
@@ -53,10 +68,14 @@ pub enum TransRequest {
         returns: oneshot::Sender<Result<(), rusqlite::Error>>,
     },
     Fetch {
-        stmt: String,
+        stmt: Query,
         values: Vec<rusqlite::types::Value>,
         returns: flume::Sender<Result<Row, rusqlite::Error>>,
     },
+    Savepoint {
+        channel: flume::Receiver<TransRequest>,
+        ready: oneshot::Sender<Result<(), rusqlite::Error>>,
+    },
     Commit {
         returns: oneshot::Sender<Result<(), rusqlite::Error>>,
     },
@@ -65,6 +84,30 @@ pub enum TransRequest {
     },
 }
 
+/// The statement a request runs
+pub enum Query {
+    /// Compiled through the connection's statement cache
+    Sql(String),
+    /// Compiled by `Prepare`, the sql is used when the statement was prepared
+    /// on another connection
+    Prepared { id: u64, sql: String },
+}
+
+impl Query {
+    fn sql(&self) -> &str {
+        match self {
+            Query::Sql(sql) | Query::Prepared { sql, .. } => sql,
+        }
+    }
+}
+
+/// A statement compiled by `Prepare`, owned by a single `Statement` handle.
+/// Sending the id on `finalize` drops it again.
+pub struct Prepared {
+    pub id: u64,
+    pub finalize: flume::Sender<u64>,
+}
+
 pub async fn open_worker(
     flags: rusqlite::OpenFlags,
     path: Option<PathBuf>,
@@ -91,7 +134,7 @@ pub fn worker(
         rusqlite::Connection::open_in_memory_with_flags(flags)
     };
 
-    let mut client = match conn {
+    let client = match conn {
         Ok(client) => {
             on_open.send(Ok(())).ok();
             client
@@ -102,14 +145,23 @@ pub fn worker(
         }
     };
 
+    let mut statements = Statements::new(&client);
+
     while let Ok(next) = rx.recv() {
+        statements.finalized();
+
         match next {
+            Request::Prepare { stmt, returns } => {
+                returns.send(statements.prepare(&stmt)).ok();
+            }
             Request::Exec {
                 stmt,
                 values,
                 returns,
             } => {
-                returns.send(execute(&client, stmt, values)).ok();
+                returns
+                    .send(execute(&client, &mut statements, stmt, values))
+                    .ok();
             }
             Request::ExecBatch { stmt, returns } => {
                 returns.send(execute_batch(&client, stmt)).ok();
@@ -119,10 +171,14 @@ pub fn worker(
                 values,
                 returns,
             } => {
-                fetch(&client, stmt, values, returns);
+                fetch(&client, &mut statements, stmt, values, returns);
             }
-            Request::Begin { channel, ready } => {
-                let trans = match client.transaction() {
+            Request::Begin {
+                options,
+                channel,
+                ready,
+            } => {
+                let trans = match begin(&client, options) {
                     Ok(ret) => {
                         ready.send(Ok(())).ok();
                         ret
@@ -133,7 +189,11 @@ pub fn worker(
                     }
                 };
 
-                transaction(trans, channel);
+                transaction(trans, &mut statements, channel);
+
+                if options.read_only {
+                    client.pragma_update(None, "query_only", false).ok();
+                }
             }
             Request::With { func, returns } => {
                 returns.send(func(&client)).ok();
@@ -142,19 +202,98 @@ pub fn worker(
     }
 }
 
+fn begin(
+    client: &rusqlite::Connection,
+    options: TransactionOptions,
+) -> Result<rusqlite::Transaction<'_>, rusqlite::Error> {
+    // Isolation levels don't apply, SQLite transactions are always serializable
+    let behavior = match options.behavior {
+        TransactionBehavior::Deferred => rusqlite::TransactionBehavior::Deferred,
+        TransactionBehavior::Immediate => rusqlite::TransactionBehavior::Immediate,
+        TransactionBehavior::Exclusive => rusqlite::TransactionBehavior::Exclusive,
+    };
+
+    // Prepared statements borrow the connection, so the transaction can't borrow it
+    // mutably. The worker doesn't take requests for the connection until it is finished.
+    let trans = rusqlite::Transaction::new_unchecked(client, behavior)?;
+
+    // Reset by the worker once the transaction is finished
+    if options.read_only {
+        trans.pragma_update(None, "query_only", true)?;
+    }
+
+    Ok(trans)
+}
+
+static STATEMENT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Statements compiled by `Prepare`, by the id of their handle.
+///
+/// Each handle gets its own statement, compiled once and kept until the handle is
+/// finalized, unlike ad-hoc queries which go through the connection's LRU statement
+/// cache. Finalized ids are sent on an unbounded channel, so finalizing never
+/// blocks and never loses the id, and the statements are dropped before the worker
+/// handles its next request.
+struct Statements<'conn> {
+    conn: &'conn rusqlite::Connection,
+    stmts: HashMap<u64, rusqlite::Statement<'conn>>,
+    finalize: flume::Sender<u64>,
+    finalized: flume::Receiver<u64>,
+}
+
+impl<'conn> Statements<'conn> {
+    fn new(conn: &'conn rusqlite::Connection) -> Statements<'conn> {
+        let (finalize, finalized) = flume::unbounded();
+        Statements {
+            conn,
+            stmts: HashMap::new(),
+            finalize,
+            finalized,
+        }
+    }
+
+    // Compiled on the connection itself, also inside a transaction, so the
+    // statement outlives it
+    fn prepare(&mut self, sql: &str) -> Result<Prepared, rusqlite::Error> {
+        let stmt = self.conn.prepare(sql)?;
+        let id = STATEMENT_ID.fetch_add(1, Ordering::Relaxed);
+        self.stmts.insert(id, stmt);
+
+        Ok(Prepared {
+            id,
+            finalize: self.finalize.clone(),
+        })
+    }
+
+    fn get(&mut self, query: &Query) -> Option<&mut rusqlite::Statement<'conn>> {
+        match query {
+            Query::Prepared { id, .. } => self.stmts.get_mut(id),
+            Query::Sql(_) => None,
+        }
+    }
+
+    fn finalized(&mut self) {
+        for id in self.finalized.try_iter() {
+            self.stmts.remove(&id);
+        }
+    }
+}
+
 fn execute<C: SqliteConn>(
     conn: &C,
-    stmt: String,
+    statements: &mut Statements<'_>,
+    stmt: Query,
     params: Vec<Value>,
 ) -> Result<QueryResult, rusqlite::Error> {
-    let stmt = conn.execute(&stmt, rusqlite::params_from_iter(params))?;
+    let params = rusqlite::params_from_iter(params);
 
-    let last_insert_id = conn.last_insert_rowid();
+    let affected_rows = match statements.get(&stmt) {
+        Some(prepared) => prepared.execute(params)?,
+        None => conn.prepare_cached(stmt.sql())?.execute(params)?,
+    } as u64;
+    let last_insert_id = last_insert_id(stmt.sql(), affected_rows, conn.last_insert_rowid());
 
-    Ok(QueryResult {
-        last_insert_id,
-        affected_rows: stmt,
-    })
+    Ok(QueryResult::new(affected_rows, last_insert_id))
 }
 
 fn execute_batch<C: SqliteConn>(conn: &C, stmt: String) -> Result<(), rusqlite::Error> {
@@ -163,19 +302,28 @@ fn execute_batch<C: SqliteConn>(conn: &C, stmt: String) -> Result<(), rusqlite::
 
 fn fetch<C: SqliteConn>(
     conn: &C,
-    stmt: String,
+    statements: &mut Statements<'_>,
+    stmt: Query,
     params: Vec<Value>,
     returns: flume::Sender<Result<Row, rusqlite::Error>>,
 ) {
-    let params = rusqlite::params_from_iter(params);
+    match statements.get(&stmt) {
+        Some(prepared) => fetch_rows(prepared, params, returns),
+        None => match conn.prepare_cached(stmt.sql()) {
+            Ok(mut cached) => fetch_rows(&mut cached, params, returns),
+            Err(err) => {
+                returns.send(Err(err)).ok();
+            }
+        },
+    }
+}
 
-    let mut stmt = match conn.prepare(&stmt) {
-        Ok(ret) => ret,
-        Err(err) => {
-            returns.send(Err(err)).ok();
-            return;
-        }
-    };
+fn fetch_rows(
+    stmt: &mut rusqlite::Statement<'_>,
+    params: Vec<Value>,
+    returns: flume::Sender<Result<Row, rusqlite::Error>>,
+) {
+    let params = rusqlite::params_from_iter(params);
 
     let mut rows = match stmt.query(params) {
         Ok(ret) => ret,
@@ -231,15 +379,26 @@ fn fetch<C: SqliteConn>(
     }
 }
 
-fn transaction(conn: rusqlite::Transaction<'_>, channel: flume::Receiver<TransRequest>) {
+// Runs a transaction, or a savepoint nested inside one, until it is committed or rolled back.
+// A savepoint takes over the worker until it is finished, just like the outer transaction.
+fn transaction<T: SqliteTrans>(
+    mut conn: T,
+    statements: &mut Statements<'_>,
+    channel: flume::Receiver<TransRequest>,
+) {
     while let Ok(next) = channel.recv() {
+        statements.finalized();
+
         match next {
+            TransRequest::Prepare { stmt, returns } => {
+                returns.send(statements.prepare(&stmt)).ok();
+            }
             TransRequest::Exec {
                 stmt,
                 values,
                 returns,
             } => {
-                returns.send(execute(&conn, stmt, values)).ok();
+                returns.send(execute(&conn, statements, stmt, values)).ok();
             }
             TransRequest::ExecBatch { stmt, returns } => {
                 returns.send(execute_batch(&conn, stmt)).ok();
@@ -249,7 +408,21 @@ fn transaction(conn: rusqlite::Transaction<'_>, channel: flume::Receiver<TransRe
                 values,
                 returns,
             } => {
-                fetch(&conn, stmt, values, returns);
+                fetch(&conn, statements, stmt, values, returns);
+            }
+            TransRequest::Savepoint { channel, ready } => {
+                let savepoint = match conn.savepoint() {
+                    Ok(ret) => {
+                        ready.send(Ok(())).ok();
+                        ret
+                    }
+                    Err(err) => {
+                        ready.send(Err(err)).ok();
+                        continue;
+                    }
+                };
+
+                transaction(savepoint, statements, channel);
             }
             TransRequest::Commit { returns } => {
                 returns.send(conn.commit()).ok();
@@ -267,8 +440,7 @@ fn transaction(conn: rusqlite::Transaction<'_>, channel: flume::Receiver<TransRe
 
 trait SqliteConn {
     fn execute_batch(&self, sql: &str) -> Result<(), rusqlite::Error>;
-    fn execute<P: rusqlite::Params>(&self, sql: &str, params: P) -> Result<usize, rusqlite::Error>;
-    fn prepare(&self, sql: &str) -> Result<rusqlite::Statement<'_>, rusqlite::Error>;
+    fn prepare_cached(&self, sql: &str) -> Result<rusqlite::CachedStatement<'_>, rusqlite::Error>;
     fn last_insert_rowid(&self) -> i64;
 }
 
@@ -277,12 +449,8 @@ impl SqliteConn for rusqlite::Connection {
         self.execute_batch(sql)
     }
 
-    fn execute<P: rusqlite::Params>(&self, sql: &str, params: P) -> Result<usize, rusqlite::Error> {
-        self.execute(sql, params)
-    }
-
-    fn prepare(&self, sql: &str) -> Result<rusqlite::Statement<'_>, rusqlite::Error> {
-        self.prepare(sql)
+    fn prepare_cached(&self, sql: &str) -> Result<rusqlite::CachedStatement<'_>, rusqlite::Error> {
+        self.prepare_cached(sql)
     }
 
     fn last_insert_rowid(&self) -> i64 {
@@ -294,15 +462,62 @@ impl SqliteConn for rusqlite::Transaction<'_> {
     fn execute_batch(&self, sql: &str) -> Result<(), rusqlite::Error> {
         (**self).execute_batch(sql)
     }
-    fn execute<P: rusqlite::Params>(&self, sql: &str, params: P) -> Result<usize, rusqlite::Error> {
-        (**self).execute(sql, params)
+
+    fn prepare_cached(&self, sql: &str) -> Result<rusqlite::CachedStatement<'_>, rusqlite::Error> {
+        (**self).prepare_cached(sql)
+    }
+
+    fn last_insert_rowid(&self) -> i64 {
+        (**self).last_insert_rowid()
+    }
+}
+
+impl SqliteConn for rusqlite::Savepoint<'_> {
+    fn execute_batch(&self, sql: &str) -> Result<(), rusqlite::Error> {
+        (**self).execute_batch(sql)
     }
 
-    fn prepare(&self, sql: &str) -> Result<rusqlite::Statement<'_>, rusqlite::Error> {
-        (**self).prepare(sql)
+    fn prepare_cached(&self, sql: &str) -> Result<rusqlite::CachedStatement<'_>, rusqlite::Error> {
+        (**self).prepare_cached(sql)
     }
 
     fn last_insert_rowid(&self) -> i64 {
         (**self).last_insert_rowid()
     }
 }
+
+trait SqliteTrans: SqliteConn {
+    fn savepoint(&mut self) -> Result<rusqlite::Savepoint<'_>, rusqlite::Error>;
+    fn commit(self) -> Result<(), rusqlite::Error>;
+    fn rollback(self) -> Result<(), rusqlite::Error>;
+}
+
+impl SqliteTrans for rusqlite::Transaction<'_> {
+    fn savepoint(&mut self) -> Result<rusqlite::Savepoint<'_>, rusqlite::Error> {
+        self.savepoint()
+    }
+
+    fn commit(self) -> Result<(), rusqlite::Error> {
+        self.commit()
+    }
+
+    fn rollback(self) -> Result<(), rusqlite::Error> {
+        self.rollback()
+    }
+}
+
+impl SqliteTrans for rusqlite::Savepoint<'_> {
+    fn savepoint(&mut self) -> Result<rusqlite::Savepoint<'_>, rusqlite::Error> {
+        self.savepoint()
+    }
+
+    fn commit(self) -> Result<(), rusqlite::Error> {
+        self.commit()
+    }
+
+    // ROLLBACK TO keeps the savepoint open, so release it afterwards
+    fn rollback(mut self) -> Result<(), rusqlite::Error> {
+        rusqlite::Savepoint::rollback(&mut self)?;
+        self.commit()
+    }
+}
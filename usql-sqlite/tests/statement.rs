@@ -0,0 +1,118 @@
+use usql_core::{Connection, Connector, Executor, Pool, Statement, Transaction};
+use usql_sqlite::{Sqlite, SqliteOptions, SqlitePool};
+use usql_value::{Value, ValueCow};
+
+const INSERT: &str = "INSERT INTO users (name) VALUES (?)";
+
+async fn pool(capacity: usize) -> SqlitePool {
+    let pool = Sqlite::create_pool(SqliteOptions::default().statement_cache_capacity(capacity))
+        .await
+        .unwrap();
+    pool.get()
+        .await
+        .unwrap()
+        .exec_batch("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)")
+        .await
+        .unwrap();
+    pool
+}
+
+fn name(name: &str) -> Vec<ValueCow<'static>> {
+    vec![ValueCow::Owned(Value::from(name))]
+}
+
+#[tokio::test]
+async fn prepare_and_finalize() {
+    let conn = pool(16).await.get().await.unwrap();
+
+    let mut stmt = conn.prepare(INSERT).await.unwrap();
+    for id in 1..=2 {
+        let ret = conn.exec(&mut stmt, name("ada")).await.unwrap();
+        assert_eq!(ret.affected_rows, 1);
+        assert_eq!(ret.last_insert_id, Some(id));
+    }
+    stmt.finalize().unwrap();
+
+    // Preparing it again compiles a fresh statement
+    let mut stmt = conn.prepare(INSERT).await.unwrap();
+    let ret = conn.exec(&mut stmt, name("grace")).await.unwrap();
+    assert_eq!(ret.last_insert_id, Some(3));
+    stmt.finalize().unwrap();
+}
+
+#[tokio::test]
+async fn prepare_invalid_sql() {
+    let conn = pool(16).await.get().await.unwrap();
+
+    assert!(conn.prepare("SELECT * FROM missing").await.is_err());
+    assert!(conn.prepare("INSERT INTO").await.is_err());
+}
+
+#[tokio::test]
+async fn statements_with_the_same_sql() {
+    let conn = pool(16).await.get().await.unwrap();
+
+    let first = conn.prepare(INSERT).await.unwrap();
+    let mut second = conn.prepare(INSERT).await.unwrap();
+
+    // Each handle owns its statement, finalizing one leaves the other alone
+    first.finalize().unwrap();
+    let ret = conn.exec(&mut second, name("ada")).await.unwrap();
+    assert_eq!(ret.last_insert_id, Some(1));
+    second.finalize().unwrap();
+}
+
+#[tokio::test]
+async fn statements_outlive_the_cache() {
+    // Nothing is kept in the statement cache
+    let conn = pool(0).await.get().await.unwrap();
+
+    let mut stmt = conn.prepare(INSERT).await.unwrap();
+    for id in 1..=3 {
+        conn.exec_batch("SELECT 1").await.unwrap();
+        let ret = conn.exec(&mut stmt, name("ada")).await.unwrap();
+        assert_eq!(ret.last_insert_id, Some(id));
+    }
+}
+
+#[tokio::test]
+async fn statements_on_another_connection() {
+    let pool = pool(16).await;
+    let first = pool.get().await.unwrap();
+    let second = pool.get().await.unwrap();
+
+    // Compiled again by the other connection's cache
+    let mut stmt = first.prepare(INSERT).await.unwrap();
+    let ret = second.exec(&mut stmt, name("ada")).await.unwrap();
+    assert_eq!(ret.affected_rows, 1);
+    drop(stmt);
+
+    // Dropping a statement finalizes it too
+    let mut stmt = first.prepare(INSERT).await.unwrap();
+    let ret = first.exec(&mut stmt, name("grace")).await.unwrap();
+    assert_eq!(ret.affected_rows, 1);
+}
+
+#[tokio::test]
+async fn statements_outlive_transactions() {
+    let mut conn = pool(16).await.get().await.unwrap();
+
+    let trans = conn.begin().await.unwrap();
+    let mut stmt = trans.prepare(INSERT).await.unwrap();
+    let ret = trans.exec(&mut stmt, name("ada")).await.unwrap();
+    assert_eq!(ret.affected_rows, 1);
+    trans.commit().await.unwrap();
+
+    let ret = conn.exec(&mut stmt, name("grace")).await.unwrap();
+    assert_eq!(ret.last_insert_id, Some(2));
+    stmt.finalize().unwrap();
+
+    // And statements prepared on the connection run inside a transaction
+    let mut stmt = conn.prepare(INSERT).await.unwrap();
+    let trans = conn.begin().await.unwrap();
+    trans.exec(&mut stmt, name("rolled back")).await.unwrap();
+    trans.rollback().await.unwrap();
+
+    let ret = conn.exec(&mut stmt, name("linus")).await.unwrap();
+    assert_eq!(ret.last_insert_id, Some(3));
+}
@@ -0,0 +1,143 @@
+use usql_core::{
+    ColumnIndex, Connection, Connector, Executor, Pool, Row, Transaction, TransactionOptions,
+    util::next,
+};
+use usql_sqlite::{Sqlite, SqliteOptions, SqlitePool};
+use usql_value::Value;
+
+async fn pool() -> SqlitePool {
+    let pool = Sqlite::create_pool(SqliteOptions::default()).await.unwrap();
+    pool.get()
+        .await
+        .unwrap()
+        .exec_batch("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)")
+        .await
+        .unwrap();
+    pool
+}
+
+async fn insert<E: Executor<Connector = Sqlite>>(exec: &E, name: &str) {
+    exec.exec_batch(&format!("INSERT INTO users (name) VALUES ('{name}')"))
+        .await
+        .unwrap();
+}
+
+async fn count<E: Executor<Connector = Sqlite>>(exec: &E) -> i64 {
+    scalar(exec, "SELECT COUNT(*) FROM users").await
+}
+
+async fn scalar<E: Executor<Connector = Sqlite>>(exec: &E, sql: &str) -> i64 {
+    let mut stmt = exec.prepare(sql).await.unwrap();
+    let mut rows = exec.query(&mut stmt, vec![]);
+    let row = next(&mut rows).await.unwrap().unwrap();
+
+    match row.get(ColumnIndex::Index(0)).unwrap().to_owned() {
+        Value::BigInt(n) => n,
+        v => panic!("unexpected value: {v:?}"),
+    }
+}
+
+#[tokio::test]
+async fn savepoint_commit_and_rollback() {
+    let mut conn = pool().await.get().await.unwrap();
+
+    let mut trans = conn.begin().await.unwrap();
+    insert(&trans, "outer").await;
+
+    let savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "committed").await;
+    savepoint.commit().await.unwrap();
+    assert_eq!(count(&trans).await, 2);
+
+    let savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "rolled back").await;
+    assert_eq!(count(&savepoint).await, 3);
+    savepoint.rollback().await.unwrap();
+    assert_eq!(count(&trans).await, 2);
+
+    trans.commit().await.unwrap();
+    assert_eq!(count(&conn).await, 2);
+}
+
+#[tokio::test]
+async fn nested_savepoints() {
+    let mut conn = pool().await.get().await.unwrap();
+
+    let mut trans = conn.begin().await.unwrap();
+
+    let mut savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "outer").await;
+    let nested = savepoint.savepoint().await.unwrap();
+    insert(&nested, "nested").await;
+    nested.commit().await.unwrap();
+    assert_eq!(count(&savepoint).await, 2);
+
+    // Rolling back the outer savepoint undoes the committed nested one too
+    savepoint.rollback().await.unwrap();
+    assert_eq!(count(&trans).await, 0);
+
+    let mut savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "outer").await;
+    let nested = savepoint.savepoint().await.unwrap();
+    insert(&nested, "rolled back").await;
+    nested.rollback().await.unwrap();
+    savepoint.commit().await.unwrap();
+
+    trans.commit().await.unwrap();
+    assert_eq!(count(&conn).await, 1);
+}
+
+#[tokio::test]
+async fn dropped_savepoint_rolls_back() {
+    let mut conn = pool().await.get().await.unwrap();
+
+    let mut trans = conn.begin().await.unwrap();
+    insert(&trans, "outer").await;
+
+    let savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "dropped").await;
+    drop(savepoint);
+    assert_eq!(count(&trans).await, 1);
+
+    // Also when the transaction is committed right after
+    let savepoint = trans.savepoint().await.unwrap();
+    insert(&savepoint, "dropped").await;
+    drop(savepoint);
+
+    trans.commit().await.unwrap();
+    assert_eq!(count(&conn).await, 1);
+}
+
+#[tokio::test]
+async fn read_only_transactions() {
+    let mut conn = pool().await.get().await.unwrap();
+    insert(&conn, "ada").await;
+
+    let read_only = || TransactionOptions::new().read_only(true);
+
+    let trans = conn.begin_with(read_only()).await.unwrap();
+    assert_eq!(scalar(&trans, "PRAGMA query_only").await, 1);
+    assert!(
+        trans
+            .exec_batch("INSERT INTO users (name) VALUES ('rejected')")
+            .await
+            .is_err()
+    );
+    assert_eq!(count(&trans).await, 1);
+    trans.commit().await.unwrap();
+
+    // query_only is reset however the transaction ends
+    assert_eq!(scalar(&conn, "PRAGMA query_only").await, 0);
+    insert(&conn, "after commit").await;
+
+    let trans = conn.begin_with(read_only()).await.unwrap();
+    trans.rollback().await.unwrap();
+    assert_eq!(scalar(&conn, "PRAGMA query_only").await, 0);
+    insert(&conn, "after rollback").await;
+
+    drop(conn.begin_with(read_only()).await.unwrap());
+    assert_eq!(scalar(&conn, "PRAGMA query_only").await, 0);
+    insert(&conn, "after drop").await;
+
+    assert_eq!(count(&conn).await, 4);
+}
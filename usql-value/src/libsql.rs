@@ -8,7 +8,7 @@ impl IntoValue for Value {
     fn into_value(self) -> libsql::Result<libsql::Value> {
         let ret = match self {
             Value::Bool(b) => libsql::Value::Integer((b).into()),
-            Value::Text(s) => libsql::Value::Text(s),
+            Value::Text(s) => libsql::Value::Text(s.into()),
             Value::Array(list) => {
                 let string = serde_json::to_string(&list).expect("json encode");
                 libsql::Value::Text(string)
@@ -28,6 +28,7 @@ impl IntoValue for Value {
                 libsql::Value::Text(string)
             }
             Value::Null => libsql::Value::Null,
+            Value::Geometry(geo) => libsql::Value::Text(geo.to_string()),
         };
 
         Ok(ret)
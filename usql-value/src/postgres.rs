@@ -1,6 +1,9 @@
-use postgres_types::{IsNull, Json, ToSql};
+use alloc::{boxed::Box, format, vec::Vec};
+use postgres_types::{FromSql, IsNull, Json, Kind, ToSql, Type};
 
-use crate::{Value, ValueRef};
+use crate::{JsonValue, Value, ValueRef};
+
+type BoxError = Box<dyn core::error::Error + Sync + Send>;
 
 impl postgres_types::ToSql for ValueRef<'_> {
     fn to_sql(
@@ -47,16 +50,16 @@ impl postgres_types::ToSql for ValueRef<'_> {
         match self {
             ValueRef::Null => Ok(IsNull::Yes),
             ValueRef::Bool(b) => b.to_sql_checked(ty, out),
-            ValueRef::SmallInt(i) => i.to_sql_checked(ty, out),
-            ValueRef::Int(i) => i.to_sql_checked(ty, out),
-            ValueRef::BigInt(i) => i.to_sql_checked(ty, out),
-            ValueRef::Float(ordered_float) => ordered_float.to_sql_checked(ty, out),
-            ValueRef::Double(ordered_float) => ordered_float.to_sql_checked(ty, out),
+            ValueRef::SmallInt(i) => int_to_sql(*i as i64, ty, out),
+            ValueRef::Int(i) => int_to_sql(*i as i64, ty, out),
+            ValueRef::BigInt(i) => int_to_sql(*i, ty, out),
+            ValueRef::Float(ordered_float) => float_to_sql(**ordered_float as f64, ty, out),
+            ValueRef::Double(ordered_float) => float_to_sql(**ordered_float, ty, out),
             ValueRef::Text(text) => text.to_sql_checked(ty, out),
             ValueRef::ByteArray(items) => items.to_sql_checked(ty, out),
             ValueRef::Date(naive_date) => naive_date.to_sql_checked(ty, out),
             ValueRef::Time(naive_time) => naive_time.to_sql_checked(ty, out),
-            ValueRef::Timestamp(naive_date_time) => naive_date_time.to_sql_checked(ty, out),
+            ValueRef::Timestamp(naive_date_time) => timestamp_to_sql(naive_date_time, ty, out),
             ValueRef::Uuid(uuid) => uuid.to_sql_checked(ty, out),
             ValueRef::Json(json_value) => Json(*json_value).to_sql_checked(ty, out),
             ValueRef::Array(values) => values.to_sql_checked(ty, out),
@@ -92,3 +95,107 @@ impl ToSql for Value {
         self.as_ref().to_sql_checked(ty, out)
     }
 }
+
+// Integer and float values are bound with the width of the parameter,
+// so `val(1)` works against both INT4 and INT8 columns
+fn int_to_sql(value: i64, ty: &Type, out: &mut bytes::BytesMut) -> Result<IsNull, BoxError> {
+    match *ty {
+        Type::INT2 => i16::try_from(value)?.to_sql(ty, out),
+        Type::INT4 => i32::try_from(value)?.to_sql(ty, out),
+        Type::INT8 => value.to_sql(ty, out),
+        Type::FLOAT4 => (value as f32).to_sql(ty, out),
+        Type::FLOAT8 => (value as f64).to_sql(ty, out),
+        _ => value.to_sql_checked(ty, out),
+    }
+}
+
+fn float_to_sql(value: f64, ty: &Type, out: &mut bytes::BytesMut) -> Result<IsNull, BoxError> {
+    match *ty {
+        Type::FLOAT4 => (value as f32).to_sql(ty, out),
+        Type::FLOAT8 => value.to_sql(ty, out),
+        _ => value.to_sql_checked(ty, out),
+    }
+}
+
+// Timestamps are UTC, like TIMESTAMPTZ columns are read, so they can be bound to both
+fn timestamp_to_sql(
+    value: &chrono::NaiveDateTime,
+    ty: &Type,
+    out: &mut bytes::BytesMut,
+) -> Result<IsNull, BoxError> {
+    match *ty {
+        Type::TIMESTAMPTZ => value.and_utc().to_sql(ty, out),
+        _ => value.to_sql_checked(ty, out),
+    }
+}
+
+impl<'a> FromSql<'a> for Value {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        let value = match *ty {
+            Type::BOOL => Value::Bool(bool::from_sql(ty, raw)?),
+            Type::CHAR => Value::SmallInt(i8::from_sql(ty, raw)? as _),
+            Type::INT2 => Value::SmallInt(i16::from_sql(ty, raw)?),
+            Type::INT4 => Value::Int(i32::from_sql(ty, raw)?),
+            Type::INT8 => Value::BigInt(i64::from_sql(ty, raw)?),
+            Type::OID => Value::BigInt(u32::from_sql(ty, raw)? as _),
+            Type::FLOAT4 => Value::Float(f32::from_sql(ty, raw)?.into()),
+            Type::FLOAT8 => Value::Double(f64::from_sql(ty, raw)?.into()),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => {
+                Value::Text(<&str>::from_sql(ty, raw)?.into())
+            }
+            Type::BYTEA => Value::ByteArray(<&[u8]>::from_sql(ty, raw)?.to_vec().into()),
+            Type::DATE => Value::Date(chrono::NaiveDate::from_sql(ty, raw)?),
+            Type::TIME => Value::Time(chrono::NaiveTime::from_sql(ty, raw)?),
+            Type::TIMESTAMP => Value::Timestamp(chrono::NaiveDateTime::from_sql(ty, raw)?),
+            // Postgres stores the instant without the offset it was written with, it is
+            // read as UTC and a timestamp written to a TIMESTAMPTZ column is taken as UTC
+            Type::TIMESTAMPTZ => {
+                Value::Timestamp(chrono::DateTime::<chrono::Utc>::from_sql(ty, raw)?.naive_utc())
+            }
+            Type::UUID => Value::Uuid(uuid::Uuid::from_sql(ty, raw)?),
+            Type::JSON | Type::JSONB => Value::Json(Json::<JsonValue>::from_sql(ty, raw)?.0),
+            _ => match ty.kind() {
+                Kind::Array(_) => Value::Array(Vec::<Value>::from_sql(ty, raw)?),
+                Kind::Domain(inner) => Value::from_sql(inner, raw)?,
+                _ => return Err(format!("unsupported postgres type: {}", ty).into()),
+            },
+        };
+
+        Ok(value)
+    }
+
+    fn from_sql_null(_ty: &Type) -> Result<Self, BoxError> {
+        Ok(Value::Null)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty {
+            Type::BOOL
+            | Type::CHAR
+            | Type::INT2
+            | Type::INT4
+            | Type::INT8
+            | Type::OID
+            | Type::FLOAT4
+            | Type::FLOAT8
+            | Type::TEXT
+            | Type::VARCHAR
+            | Type::BPCHAR
+            | Type::NAME
+            | Type::UNKNOWN
+            | Type::BYTEA
+            | Type::DATE
+            | Type::TIME
+            | Type::TIMESTAMP
+            | Type::TIMESTAMPTZ
+            | Type::UUID
+            | Type::JSON
+            | Type::JSONB => true,
+            _ => match ty.kind() {
+                Kind::Array(member) => Value::accepts(member),
+                Kind::Domain(inner) => Value::accepts(inner),
+                _ => false,
+            },
+        }
+    }
+}
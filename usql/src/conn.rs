@@ -1,9 +1,13 @@
-use alloc::boxed::Box;
-use usql_core::{Connection, Connector, Executor, util::next};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use usql_builder::{SqlStmt, StatementExt, schema::RebuildTable};
+use usql_core::{
+    Connection, Connector, DatabaseInfo, Executor, QueryResult, System, TransactionOptions,
+    util::next,
+};
 
 use crate::{
-    error::Error, query::IntoQuery, row::Row, stmt::Stmt, stream::QueryStream, target::Target,
-    trans::Trans,
+    error::Error, query::IntoQuery, retry::Retry, row::Row, stmt::Stmt, stream::QueryStream,
+    target::Target, trans::Trans,
 };
 
 pub struct Conn<B>
@@ -43,6 +47,130 @@ where
         Ok(Trans::new(trans))
     }
 
+    pub async fn begin_with<'a>(
+        &'a mut self,
+        options: TransactionOptions,
+    ) -> Result<Trans<'a, B>, Error<B>> {
+        let trans = self
+            .conn
+            .begin_with(options)
+            .await
+            .map_err(Error::connector)?;
+        Ok(Trans::new(trans))
+    }
+
+    /// Run `func` inside a transaction.
+    ///
+    /// The transaction is committed when `func` returns `Ok` and rolled back when it returns `Err`.
+    /// If `func` panics the transaction is dropped while unwinding, which rolls it back.
+    pub async fn transaction<T, F>(&mut self, func: F) -> Result<T, Error<B>>
+    where
+        F: AsyncFnMut(&mut Trans<'_, B>) -> Result<T, Error<B>>,
+    {
+        self.transaction_with(TransactionOptions::default(), Retry::never(), func)
+            .await
+    }
+
+    /// Like `transaction`, but starts the transaction with `options`
+    /// and runs `func` again, in a new transaction, when it fails with a retryable error.
+    /// Waits between attempts when `retry` has a backoff.
+    pub async fn transaction_with<T, F>(
+        &mut self,
+        options: TransactionOptions,
+        retry: Retry,
+        mut func: F,
+    ) -> Result<T, Error<B>>
+    where
+        F: AsyncFnMut(&mut Trans<'_, B>) -> Result<T, Error<B>>,
+    {
+        let mut retries = 0;
+        loop {
+            let mut trans = self.begin_with(options).await?;
+
+            let ret = match func(&mut trans).await {
+                Ok(ret) => trans.commit().await.map(|_| ret),
+                Err(err) => {
+                    // The error from func is the one worth reporting
+                    trans.rollback().await.ok();
+                    Err(err)
+                }
+            };
+
+            match ret {
+                Err(err) if err.is_retryable() && retries < retry.retries() => {
+                    retry.wait(retries).await;
+                    retries += 1;
+                }
+                ret => return ret,
+            }
+        }
+    }
+
+    /// Run a table rebuild, see [`RebuildTable`].
+    ///
+    /// On SQLite the steps run in a transaction with foreign keys turned off,
+    /// which is rolled back when a step fails or `PRAGMA foreign_key_check`
+    /// finds a violation. Foreign keys are turned on again if they were before.
+    pub async fn rebuild_table<'a, S>(
+        &mut self,
+        rebuild: RebuildTable<'a, S>,
+    ) -> Result<(), Error<B>>
+    where
+        S: StatementExt<'a>,
+    {
+        let dialect = self.conn.db_info().variant();
+        let steps = rebuild.steps(dialect)?;
+
+        if !matches!(dialect, System::Sqlite | System::LibSql) {
+            for step in steps {
+                self.exec(step).await?;
+            }
+            return Ok(());
+        }
+
+        // PRAGMA foreign_keys has no effect inside a transaction
+        let foreign_keys: i64 = self.fetch_one("PRAGMA foreign_keys").await?.try_get(0)?;
+        self.exec("PRAGMA foreign_keys = OFF").await?;
+
+        let ret = self.rebuild_steps(steps).await;
+
+        let restored = match foreign_keys {
+            0 => Ok(()),
+            _ => self.exec("PRAGMA foreign_keys = ON").await.map(|_| ()),
+        };
+
+        ret.and(restored)
+    }
+
+    async fn rebuild_steps(&mut self, steps: Vec<SqlStmt<'_>>) -> Result<(), Error<B>> {
+        let trans = self.begin().await?;
+
+        let ret = async {
+            for step in steps {
+                trans.exec(step).await?;
+            }
+
+            match trans.fetch_one("PRAGMA foreign_key_check").await {
+                Ok(row) => {
+                    let table: String = row.try_get(0)?;
+                    Err(Error::query(format!("foreign key violation in {table}")))
+                }
+                Err(Error::NotFound) => Ok(()),
+                Err(err) => Err(err),
+            }
+        }
+        .await;
+
+        match ret {
+            Ok(()) => trans.commit().await,
+            Err(err) => {
+                // The error from the failed step is the one worth reporting
+                trans.rollback().await.ok();
+                Err(err)
+            }
+        }
+    }
+
     pub async fn fetch<'this, 'query, 'stream, Q>(
         &'this self,
         query: Q,
@@ -78,7 +206,7 @@ where
         }
     }
 
-    pub async fn exec<'this, 'query, Q>(&'this self, query: Q) -> Result<(), Error<B>>
+    pub async fn exec<'this, 'query, Q>(&'this self, query: Q) -> Result<QueryResult, Error<B>>
     where
         Q: IntoQuery<'query, B>,
     {
@@ -87,9 +215,7 @@ where
         self.conn
             .exec(query.stmt.as_mut()?, query.bindings)
             .await
-            .map_err(Error::connector)?;
-
-        Ok(())
+            .map_err(Error::connector)
     }
 }
 
@@ -128,7 +254,8 @@ where
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: alloc::vec::Vec<usql_value::ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
         self.conn.exec(stmt, params)
     }
 
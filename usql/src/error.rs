@@ -1,6 +1,6 @@
 use alloc::boxed::Box;
 use core::fmt;
-use usql_core::Connector;
+use usql_core::{Connector, DatabaseError, ErrorKind};
 
 pub enum Error<B: Connector> {
     Connector(B::Error),
@@ -69,6 +69,40 @@ impl<B: Connector> Error<B> {
     {
         Error::Unknown(error.into())
     }
+
+    /// The database error classified across connectors, `ErrorKind::Other` for
+    /// errors that didn't come from the database.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Connector(err) => err.kind(),
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// Name of the violated constraint, when the database reports it
+    pub fn constraint(&self) -> Option<&str> {
+        match self {
+            Error::Connector(err) => err.constraint(),
+            _ => None,
+        }
+    }
+
+    /// Name of the table the error relates to, when the database reports it
+    pub fn table(&self) -> Option<&str> {
+        match self {
+            Error::Connector(err) => err.table(),
+            _ => None,
+        }
+    }
+
+    /// Whether the transaction that failed with this error may succeed when run again,
+    /// like a Postgres serialization failure or a busy SQLite database.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Connector(err) => err.is_retryable(),
+            _ => false,
+        }
+    }
 }
 
 impl<B: Connector> From<usql_builder::Error> for Error<B> {
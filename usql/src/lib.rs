@@ -7,6 +7,7 @@ mod error;
 mod from_row;
 mod pool;
 mod query;
+mod retry;
 mod row;
 mod stmt;
 mod stream;
@@ -24,6 +25,7 @@ pub use self::{
     from_row::FromRow,
     pool::Pool,
     query::IntoQuery,
+    retry::{Retry, Sleep},
     row::Row,
     stream::{FromRowStream, QueryStream},
     target::Target,
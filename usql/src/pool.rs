@@ -1,5 +1,5 @@
-use crate::{conn::Conn, error::Error};
-use usql_core::{Connector, Pool as _};
+use crate::{conn::Conn, error::Error, retry::Retry, trans::Trans};
+use usql_core::{Connector, Pool as _, TransactionOptions};
 
 pub struct Pool<B: Connector> {
     pool: B::Pool,
@@ -36,3 +36,36 @@ impl<B: Connector> Pool<B> {
             .map_err(Error::connector)
     }
 }
+
+impl<B> Pool<B>
+where
+    B: Connector,
+    B::Error: core::error::Error + Send + Sync,
+    B::Statement: 'static,
+{
+    /// Run `func` inside a transaction on a connection from the pool,
+    /// see `Conn::transaction`.
+    pub async fn transaction<T, F>(&self, func: F) -> Result<T, Error<B>>
+    where
+        F: AsyncFnMut(&mut Trans<'_, B>) -> Result<T, Error<B>>,
+    {
+        self.conn().await?.transaction(func).await
+    }
+
+    /// Run `func` inside a transaction on a connection from the pool,
+    /// see `Conn::transaction_with`.
+    pub async fn transaction_with<T, F>(
+        &self,
+        options: TransactionOptions,
+        retry: Retry,
+        func: F,
+    ) -> Result<T, Error<B>>
+    where
+        F: AsyncFnMut(&mut Trans<'_, B>) -> Result<T, Error<B>>,
+    {
+        self.conn()
+            .await?
+            .transaction_with(options, retry, func)
+            .await
+    }
+}
@@ -0,0 +1,112 @@
+use core::{hash::Hash, pin::Pin, time::Duration};
+
+use alloc::boxed::Box;
+
+/// Waits out the delay before a transaction is run again, this crate doesn't
+/// depend on a runtime so it has to be provided,
+/// e.g. `|delay| Box::pin(tokio::time::sleep(delay))`.
+pub type Sleep = fn(Duration) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// How many times a transaction is run again when it fails with a retryable error,
+/// see `Error::is_retryable`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Retry {
+    retries: u32,
+    backoff: Option<Backoff>,
+}
+
+impl Retry {
+    pub const fn never() -> Retry {
+        Retry {
+            retries: 0,
+            backoff: None,
+        }
+    }
+
+    pub const fn times(retries: u32) -> Retry {
+        Retry {
+            retries,
+            backoff: None,
+        }
+    }
+
+    /// Wait before each retry, starting at `initial` and doubling up to `max`.
+    /// Without a backoff the transaction is run again right away.
+    pub const fn backoff(mut self, initial: Duration, max: Duration, sleep: Sleep) -> Retry {
+        self.backoff = Some(Backoff {
+            initial,
+            max,
+            sleep,
+        });
+        self
+    }
+
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// The delay before the `retry`th retry, counting from 0.
+    pub fn delay(&self, retry: u32) -> Duration {
+        match &self.backoff {
+            Some(backoff) => backoff
+                .initial
+                .saturating_mul(2u32.saturating_pow(retry))
+                .min(backoff.max),
+            None => Duration::ZERO,
+        }
+    }
+
+    pub(crate) async fn wait(&self, retry: u32) {
+        if let Some(backoff) = &self.backoff {
+            (backoff.sleep)(self.delay(retry)).await;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Backoff {
+    initial: Duration,
+    max: Duration,
+    sleep: Sleep,
+}
+
+impl PartialEq for Backoff {
+    fn eq(&self, other: &Self) -> bool {
+        self.initial == other.initial
+            && self.max == other.max
+            && core::ptr::fn_addr_eq(self.sleep, other.sleep)
+    }
+}
+
+impl Eq for Backoff {}
+
+impl Hash for Backoff {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.initial.hash(state);
+        self.max.hash(state);
+        (self.sleep as usize).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec::Vec};
+    use core::time::Duration;
+
+    use super::Retry;
+
+    #[test]
+    fn test_delay() {
+        let retry =
+            Retry::times(5).backoff(Duration::from_millis(10), Duration::from_millis(50), |_| {
+                Box::pin(async {})
+            });
+
+        let delays = (0..5)
+            .map(|n| retry.delay(n).as_millis())
+            .collect::<Vec<_>>();
+        assert_eq!(delays, [10, 20, 40, 50, 50]);
+
+        assert_eq!(Retry::times(5).delay(3), Duration::ZERO);
+    }
+}
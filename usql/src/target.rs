@@ -1,4 +1,4 @@
-use usql_core::{Connection, Connector};
+use usql_core::{Connection, Connector, QueryResult};
 
 use crate::{Conn, Error, IntoQuery, QueryStream, Row, Trans, stmt::Stmt};
 
@@ -41,7 +41,7 @@ where
         }
     }
 
-    pub async fn exec<Q>(&self, query: Q) -> Result<(), Error<B>>
+    pub async fn exec<Q>(&self, query: Q) -> Result<QueryResult, Error<B>>
     where
         for<'a> Q: IntoQuery<'a, B>,
     {
@@ -1,18 +1,28 @@
+use core::marker::PhantomData;
+
 use alloc::boxed::Box;
-use usql_core::{Connection, Connector, Executor, Transaction, util::next};
+use usql_core::{Connection, Connector, Executor, QueryResult, Transaction, util::next};
 
 use crate::{Error, IntoQuery, QueryStream, Row, stmt::Stmt, target::Target};
 
-pub struct Trans<'a, B: Connector>
-where
+/// A transaction, or a savepoint nested inside one when `T` is a savepoint.
+pub struct Trans<
+    'a,
+    B: Connector,
+    T = <<B as Connector>::Connection as Connection>::Transaction<'a>,
+> where
     B::Connection: 'a,
 {
-    trans: <B::Connection as Connection>::Transaction<'a>,
+    trans: T,
+    connector: PhantomData<(&'a (), B)>,
 }
 
 impl<'a, B: Connector> Trans<'a, B> {
     pub fn new(trans: <B::Connection as Connection>::Transaction<'a>) -> Trans<'a, B> {
-        Trans { trans }
+        Trans {
+            trans,
+            connector: PhantomData,
+        }
     }
 
     pub fn into_target(self) -> Target<'a, B> {
@@ -20,13 +30,26 @@ impl<'a, B: Connector> Trans<'a, B> {
     }
 }
 
-impl<'c, B: Connector> Trans<'c, B>
+impl<'c, B, T> Trans<'c, B, T>
 where
+    B: Connector,
     B::Connection: 'c,
-    <B::Connection as Connection>::Transaction<'c>: Send + Sync,
+    T: Transaction<'c, Connector = B>,
     B::Error: core::error::Error + Send + Sync,
     B::Statement: 'static,
 {
+    /// Start a nested transaction, backed by a savepoint.
+    ///
+    /// Committing it releases its work into this transaction,
+    /// rolling it back undoes only what was done since it was created.
+    pub async fn savepoint(&mut self) -> Result<Trans<'_, B, T::Savepoint<'_>>, Error<B>> {
+        let trans = self.trans.savepoint().await.map_err(Error::connector)?;
+        Ok(Trans {
+            trans,
+            connector: PhantomData,
+        })
+    }
+
     pub async fn prepare(&self, sql: &str) -> Result<Stmt<B>, Error<B>> {
         let stmt = self.trans.prepare(sql).await.map_err(Error::connector)?;
         Ok(Stmt::new(stmt))
@@ -62,7 +85,7 @@ where
         }
     }
 
-    pub async fn exec<Q>(&self, query: Q) -> Result<(), Error<B>>
+    pub async fn exec<Q>(&self, query: Q) -> Result<QueryResult, Error<B>>
     where
         for<'a> Q: IntoQuery<'a, B>,
     {
@@ -71,9 +94,7 @@ where
         self.trans
             .exec(query.stmt.as_mut()?, query.bindings)
             .await
-            .map_err(Error::connector)?;
-
-        Ok(())
+            .map_err(Error::connector)
     }
 
     pub async fn commit(self) -> Result<(), Error<B>> {
@@ -85,9 +106,10 @@ where
     }
 }
 
-impl<'c, B> Executor for Trans<'c, B>
+impl<'c, B, T> Executor for Trans<'c, B, T>
 where
     B: Connector,
+    T: Transaction<'c, Connector = B>,
 {
     type Connector = B;
 
@@ -120,7 +142,8 @@ where
         &'a self,
         stmt: &'a mut <Self::Connector as Connector>::Statement,
         params: alloc::vec::Vec<usql_value::ValueCow<'a>>,
-    ) -> impl Future<Output = Result<(), <Self::Connector as Connector>::Error>> + Send + 'a {
+    ) -> impl Future<Output = Result<QueryResult, <Self::Connector as Connector>::Error>> + Send + 'a
+    {
         self.trans.exec(stmt, params)
     }
 
@@ -0,0 +1,121 @@
+use futures::executor::block_on;
+use usql::{
+    Conn, Pool,
+    builder::schema::{
+        AlterColumnType, Column, ColumnType, CreateTable, ForeignKey, ReferentialAction,
+        alter_table, create_table,
+    },
+};
+use usql_sqlite::{Sqlite, SqliteOptions};
+
+async fn conn() -> Conn<Sqlite> {
+    let pool = Pool::<Sqlite>::open(SqliteOptions::default())
+        .await
+        .expect("pool");
+    let conn = pool.conn().await.unwrap();
+
+    for sql in [
+        "PRAGMA foreign_keys = ON",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, age INTEGER)",
+        "CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users (id) ON DELETE CASCADE)",
+        "INSERT INTO users (id, age) VALUES (1, 36), (2, 42)",
+        "INSERT INTO posts (id, user_id) VALUES (10, 1), (11, 2)",
+    ] {
+        conn.exec(sql).await.unwrap();
+    }
+
+    conn
+}
+
+async fn scalar(conn: &Conn<Sqlite>, sql: &str) -> i64 {
+    conn.fetch_one(sql).await.unwrap().try_get(0).unwrap()
+}
+
+fn posts<'a>() -> CreateTable<'a> {
+    create_table("posts")
+        .column(Column::new("id", ColumnType::Int).primary_key())
+        .column(
+            Column::new("user_id", ColumnType::Int)
+                .foreign_key(ForeignKey::new("users", "id").on_delete(ReferentialAction::Cascade)),
+        )
+}
+
+#[test]
+fn rebuild_keeps_children() {
+    block_on(async {
+        let mut conn = conn().await;
+
+        conn.rebuild_table(
+            alter_table("users", AlterColumnType("age", ColumnType::BigInt)).sqlite_rebuild(
+                create_table("users")
+                    .column(Column::new("id", ColumnType::Int).primary_key())
+                    .column(Column::new("age", ColumnType::BigInt)),
+            ),
+        )
+        .await
+        .unwrap();
+
+        // Dropping the old table didn't cascade to the posts
+        assert_eq!(scalar(&conn, "SELECT COUNT(*) FROM users").await, 2);
+        assert_eq!(scalar(&conn, "SELECT COUNT(*) FROM posts").await, 2);
+        assert_eq!(scalar(&conn, "PRAGMA foreign_keys").await, 1);
+    })
+}
+
+#[test]
+fn rebuild_rolls_back_on_violations() {
+    block_on(async {
+        let mut conn = conn().await;
+
+        // Every post ends up pointing at a user that doesn't exist
+        let err = conn
+            .rebuild_table(
+                alter_table("posts", AlterColumnType("user_id", ColumnType::BigInt))
+                    .sqlite_rebuild(posts())
+                    .copy_columns(vec![
+                        ("id".into(), "id".into()),
+                        ("id".into(), "user_id".into()),
+                    ]),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("foreign key violation in posts"));
+
+        assert_eq!(scalar(&conn, "SELECT SUM(user_id) FROM posts").await, 3);
+        assert_eq!(
+            scalar(
+                &conn,
+                "SELECT COUNT(*) FROM sqlite_schema WHERE name LIKE '_usql_new_%'"
+            )
+            .await,
+            0
+        );
+        assert_eq!(scalar(&conn, "PRAGMA foreign_keys").await, 1);
+    })
+}
+
+#[test]
+fn rebuild_rolls_back_failed_steps() {
+    block_on(async {
+        let mut conn = conn().await;
+        conn.exec("PRAGMA foreign_keys = OFF").await.unwrap();
+
+        let err = conn
+            .rebuild_table(
+                alter_table("posts", AlterColumnType("user_id", ColumnType::BigInt))
+                    .sqlite_rebuild(posts())
+                    .copy_columns(vec![
+                        ("id".into(), "id".into()),
+                        ("missing".into(), "user_id".into()),
+                    ]),
+            )
+            .await;
+        assert!(err.is_err());
+
+        // The transaction was rolled back and foreign keys were left off
+        conn.exec("BEGIN").await.unwrap();
+        conn.exec("ROLLBACK").await.unwrap();
+        assert_eq!(scalar(&conn, "SELECT COUNT(*) FROM posts").await, 2);
+        assert_eq!(scalar(&conn, "PRAGMA foreign_keys").await, 0);
+    })
+}
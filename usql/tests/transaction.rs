@@ -0,0 +1,121 @@
+use std::{sync::Mutex, time::Duration};
+
+use futures::executor::block_on;
+use usql::{Error, Pool, Retry, core::TransactionOptions};
+use usql_sqlite::{Sqlite, SqliteError, SqliteOptions};
+
+fn busy() -> Error<Sqlite> {
+    Error::Connector(SqliteError::Sqlite(rusqlite::Error::SqliteFailure(
+        rusqlite::ffi::Error::new(5),
+        None,
+    )))
+}
+
+async fn pool() -> Pool<Sqlite> {
+    let pool = Pool::<Sqlite>::open(SqliteOptions::default())
+        .await
+        .expect("pool");
+    pool.conn()
+        .await
+        .unwrap()
+        .exec("CREATE TABLE IF NOT EXISTS users (id INTEGER PRIMARY KEY, name TEXT)")
+        .await
+        .unwrap();
+    pool
+}
+
+async fn count(pool: &Pool<Sqlite>) -> i64 {
+    pool.conn()
+        .await
+        .unwrap()
+        .fetch_one("SELECT COUNT(*) FROM users")
+        .await
+        .unwrap()
+        .try_get(0)
+        .unwrap()
+}
+
+#[test]
+fn retries_stop_after_times() {
+    block_on(async {
+        let pool = pool().await;
+
+        let mut attempts = 0;
+        let err = pool
+            .transaction_with(
+                TransactionOptions::default(),
+                Retry::times(2),
+                async |trans| {
+                    attempts += 1;
+                    trans
+                        .exec("INSERT INTO users (name) VALUES ('ada')")
+                        .await?;
+                    Err::<(), _>(busy())
+                },
+            )
+            .await
+            .unwrap_err();
+
+        assert!(err.is_retryable());
+        assert_eq!(attempts, 3);
+        // Every attempt was rolled back
+        assert_eq!(count(&pool).await, 0);
+    })
+}
+
+#[test]
+fn non_retryable_errors_return_first_time() {
+    block_on(async {
+        let pool = pool().await;
+
+        let mut attempts = 0;
+        let err = pool
+            .transaction_with(TransactionOptions::default(), Retry::times(5), async |_| {
+                attempts += 1;
+                Err::<(), _>(Error::Connector(SqliteError::NotFound))
+            })
+            .await
+            .unwrap_err();
+
+        assert!(!err.is_retryable());
+        assert!(matches!(err, Error::Connector(SqliteError::NotFound)));
+        assert_eq!(attempts, 1);
+    })
+}
+
+#[test]
+fn backoff_between_retries() {
+    static DELAYS: Mutex<Vec<Duration>> = Mutex::new(Vec::new());
+
+    block_on(async {
+        let pool = pool().await;
+
+        let retry =
+            Retry::times(3).backoff(Duration::from_millis(10), Duration::from_secs(1), |delay| {
+                DELAYS.lock().unwrap().push(delay);
+                Box::pin(async {})
+            });
+
+        let mut attempts = 0;
+        let ret = pool
+            .transaction_with(TransactionOptions::default(), retry, async |trans| {
+                attempts += 1;
+                if attempts < 3 {
+                    return Err(busy());
+                }
+                trans
+                    .exec("INSERT INTO users (name) VALUES ('grace')")
+                    .await?;
+                Ok(attempts)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(ret, 3);
+        assert_eq!(count(&pool).await, 1);
+        assert_eq!(
+            *DELAYS.lock().unwrap(),
+            [Duration::from_millis(10), Duration::from_millis(20)]
+        );
+    })
+}